@@ -0,0 +1,414 @@
+//! Rust bindings for `ui.h`.
+//!
+//! Pre-generated bindings are checked in under `bindings/<target>/ui.rs`
+//! (`bindings/ng/<target>/ui.rs` for libui-ng) and used as is by default.
+//! With the `bindgen` feature they are generated from the header at build
+//! time instead; setting `LIBUI_SYS_UPDATE_BINDINGS` additionally writes the
+//! result back into the source tree, which is how the checked-in files are
+//! refreshed after a libui update.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::BuildError;
+
+/// Puts the bindings for `header` into `out_dir/bindings.rs`, and those for
+/// the platform header of the target's backend next to `header` into
+/// `out_dir/ui_<backend>.rs`. Returns the files written.
+pub fn write(header: &Path, out_dir: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let target = env::var("TARGET").unwrap();
+
+    // Lets the tests compare fresh bindings with the checked-in ones.
+    println!("cargo:rustc-env=LIBUI_SYS_TARGET={}", target);
+
+    // The crate has a module for the native control API of the backend,
+    // gated on this cfg.
+    println!("cargo:rustc-check-cfg=cfg(libui_backend, values(\"unix\", \"windows\", \"darwin\"))");
+    let mut files = vec![(None, "bindings.rs".to_owned(), "ui".to_owned())];
+    if let Some(backend) = backend() {
+        let platform_header = header.with_file_name(format!("ui_{}.h", backend));
+        if cfg!(feature = "bindgen") && !platform_header.exists() {
+            crate::warn(&format!(
+                "{} does not exist, the `{}` module is left out",
+                platform_header.display(),
+                backend
+            ));
+        } else {
+            println!("cargo:rerun-if-changed={}", platform_header.display());
+            println!("cargo:rustc-cfg=libui_backend=\"{}\"", backend);
+            let stem = format!("ui_{}", backend);
+            files.push((Some(backend), format!("{}.rs", stem), stem));
+        }
+    }
+
+    if cfg!(feature = "bindgen") {
+        // bindgen and clang-sys, which finds libclang for it, read these
+        // without telling Cargo.
+        let vars = [
+            "LIBCLANG_PATH".to_owned(),
+            "CLANG_PATH".to_owned(),
+            "LLVM_CONFIG_PATH".to_owned(),
+            "BINDGEN_EXTRA_CLANG_ARGS".to_owned(),
+            format!("BINDGEN_EXTRA_CLANG_ARGS_{}", target),
+            format!("BINDGEN_EXTRA_CLANG_ARGS_{}", target.replace('-', "_")),
+        ];
+        for var in &vars {
+            crate::env_var_os(var);
+        }
+    }
+
+    let mut written = Vec::new();
+    for (backend, out_name, stem) in files {
+        let out_file = out_dir.join(out_name);
+        let pregenerated = pregenerated_path(&target, &stem);
+        written.push(out_file.clone());
+
+        if cfg!(feature = "bindgen") {
+            generate(header, backend, &out_file)?;
+            if crate::env_var_os("LIBUI_SYS_UPDATE_BINDINGS").is_some() {
+                fs::create_dir_all(pregenerated.parent().unwrap())?;
+                fs::copy(&out_file, &pregenerated)?;
+            }
+            continue;
+        }
+
+        if !pregenerated.exists() {
+            return Err(BuildError::Other(format!(
+                "There are no pre-generated bindings for {} at {}.\n\
+                 Enable the `bindgen` feature to generate them at build time.",
+                target,
+                pregenerated.display()
+            )));
+        }
+        println!("cargo:rerun-if-changed={}", pregenerated.display());
+        fs::copy(&pregenerated, &out_file)?;
+    }
+
+    // Only the tests of this crate need the andlabs/libui bindings, so they
+    // are generated on request.
+    println!("cargo:rustc-check-cfg=cfg(libui_andlabs)");
+    let andlabs = crate::env_var_os("LIBUI_SYS_COMPARE_ANDLABS").is_some();
+    if andlabs && cfg!(feature = "bindgen") && crate::is_ng() && !crate::is_dlopen() {
+        write_andlabs(out_dir)?;
+        println!("cargo:rustc-cfg=libui_andlabs");
+    }
+    Ok(written)
+}
+
+/// Puts the bindings for the ui.h of andlabs/libui into
+/// `out_dir/andlabs.rs`, which the tests compare the libui-ng ones with.
+fn write_andlabs(out_dir: &Path) -> Result<(), BuildError> {
+    let header = crate::manifest_dir().join("libui").join("ui.h");
+    if !header.exists() {
+        return Err(BuildError::Other(format!(
+            "LIBUI_SYS_COMPARE_ANDLABS is set, but {} does not exist.\n\
+             Check out the libui submodule to test the libui-ng API against it.",
+            header.display()
+        )));
+    }
+    println!("cargo:rerun-if-changed={}", header.display());
+    generate(&header, None, &out_dir.join("andlabs.rs"))
+}
+
+/// The libui backend used for the target, named like its platform header.
+fn backend() -> Option<&'static str> {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    match target_os.as_str() {
+        "windows" => Some("windows"),
+        "macos" => Some("darwin"),
+        os if crate::is_unix_backend(os) => Some("unix"),
+        _ => None,
+    }
+}
+
+fn pregenerated_path(target: &str, stem: &str) -> PathBuf {
+    let mut path = crate::manifest_dir().join("bindings");
+    if crate::is_ng() {
+        path.push("ng");
+    }
+    // Bindings for loading libui at runtime have no extern blocks, so they
+    // are kept apart from the linked ones.
+    let suffix = if crate::is_dlopen() { "_dlopen" } else { "" };
+    path.join(target).join(format!("{}{}.rs", stem, suffix))
+}
+
+/// Generates the bindings for `header`, or for the platform header of
+/// `backend` if given.
+#[cfg(feature = "bindgen")]
+fn generate(header: &Path, backend: Option<&str>, out_file: &Path) -> Result<(), BuildError> {
+    let mut builder = bindgen::Builder::default()
+        .clang_args(crate::cross::clang_args())
+        // Nothing in the bindings needs std.
+        .use_core()
+        .ctypes_prefix("::core::ffi")
+        // Enums become newtypes with the variants as associated constants,
+        // so that values of different enums cannot be mixed up. Flag sets
+        // get the bit operators on top.
+        .bitfield_enum("uiModifiers")
+        .newtype_enum("ui[A-Z].*")
+        // The headers document their declarations with plain comments, which
+        // clang only attaches to them when asked to.
+        .clang_arg("-fparse-all-comments")
+        .generate_comments(true)
+        .parse_callbacks(Box::new(Comments));
+    // With the `dlopen` feature the functions become fields of a struct
+    // loading them with libloading, `LibUi` for ui.h and e.g. `LibUiUnix`
+    // for ui_unix.h. Missing functions are reported by `missing_symbols`
+    // rather than failing the whole load.
+    let library_name = match backend {
+        None => "LibUi".to_owned(),
+        Some(backend) => format!("LibUi{}{}", backend[..1].to_uppercase(), &backend[1..]),
+    };
+    if crate::is_dlopen() {
+        builder = builder
+            .dynamic_library_name(&library_name)
+            .dynamic_link_require_all(false);
+    }
+    let (builder, name) = match backend {
+        None => {
+            let contents = named_enums(&fs::read_to_string(header)?);
+            let builder = builder
+                .header_contents(header.to_str().unwrap(), &contents)
+                // Only libui's own items, not what the system headers it
+                // includes declare, like the `uint*_t` of stdint.h. `struct
+                // tm` is part of the API through uiDateTimePickerTime.
+                .allowlist_function("ui[A-Z].*")
+                .allowlist_type("ui[A-Z].*")
+                .allowlist_var("ui[A-Z].*")
+                .allowlist_type("tm");
+            (builder, header.to_owned())
+        }
+        Some(backend) => (
+            platform_builder(builder, header, backend),
+            header.with_file_name(format!("ui_{}.h", backend)),
+        ),
+    };
+    let bindings = builder.generate().map_err(|err| {
+        format!(
+            "Unable to generate bindings for {}: {}",
+            name.display(),
+            err
+        )
+    })?;
+
+    let mut file = syn::parse_file(&bindings.to_string()).map_err(|err| {
+        format!(
+            "Unable to parse the bindings generated for {}: {}",
+            name.display(),
+            err
+        )
+    })?;
+    crate::callbacks::add_aliases(&mut file);
+    syn::visit_mut::VisitMut::visit_file_mut(&mut LineDocs, &mut file);
+    fs::write(out_file, prettyplease::unparse(&file))?;
+    Ok(())
+}
+
+/// Turns the comments of the libui headers into rustdoc.
+#[cfg(feature = "bindgen")]
+#[derive(Debug)]
+struct Comments;
+
+#[cfg(feature = "bindgen")]
+impl bindgen::callbacks::ParseCallbacks for Comments {
+    fn process_comment(&self, comment: &str) -> Option<String> {
+        let lines: Vec<String> = comment
+            .lines()
+            .map(str::trim)
+            // Notes for libui's developers rather than its users.
+            .filter(|line| !line.starts_with("TODO"))
+            .skip_while(|line| line.is_empty())
+            .map(|line| format!(" {}", code_spans(&escape_markdown(line))))
+            .map(|line| line.trim_end().to_owned())
+            .collect();
+        Some(lines.join("\n").trim_end().to_owned())
+    }
+}
+
+/// Splits the documentation bindgen puts into a single `#[doc]` attribute
+/// per comment into one attribute per line, which prettyplease prints as
+/// `///` lines rather than as a `/** */` block.
+#[cfg(feature = "bindgen")]
+struct LineDocs;
+
+#[cfg(feature = "bindgen")]
+impl LineDocs {
+    fn split(attrs: &mut Vec<syn::Attribute>) {
+        let mut split = Vec::with_capacity(attrs.len());
+        for attr in attrs.drain(..) {
+            match Self::doc(&attr) {
+                Some(doc) if doc.contains('\n') => {
+                    for line in doc.lines() {
+                        split.push(syn::parse_quote!(#[doc = #line]));
+                    }
+                }
+                _ => split.push(attr),
+            }
+        }
+        *attrs = split;
+    }
+
+    /// The text of `attr` if it is a `#[doc = "..."]` attribute.
+    fn doc(attr: &syn::Attribute) -> Option<String> {
+        let meta = match &attr.meta {
+            syn::Meta::NameValue(meta) if meta.path.is_ident("doc") => meta,
+            _ => return None,
+        };
+        match &meta.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(doc),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        }
+    }
+}
+
+/// The items bindgen documents: types and their fields, constants
+/// including the values of enums, and functions, also those of the `dlopen`
+/// library struct.
+#[cfg(feature = "bindgen")]
+impl syn::visit_mut::VisitMut for LineDocs {
+    fn visit_item_struct_mut(&mut self, item: &mut syn::ItemStruct) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_item_struct_mut(self, item);
+    }
+
+    fn visit_item_union_mut(&mut self, item: &mut syn::ItemUnion) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_item_union_mut(self, item);
+    }
+
+    fn visit_item_type_mut(&mut self, item: &mut syn::ItemType) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_item_type_mut(self, item);
+    }
+
+    fn visit_item_const_mut(&mut self, item: &mut syn::ItemConst) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_item_const_mut(self, item);
+    }
+
+    fn visit_field_mut(&mut self, field: &mut syn::Field) {
+        Self::split(&mut field.attrs);
+        syn::visit_mut::visit_field_mut(self, field);
+    }
+
+    fn visit_impl_item_const_mut(&mut self, item: &mut syn::ImplItemConst) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_impl_item_const_mut(self, item);
+    }
+
+    fn visit_impl_item_fn_mut(&mut self, item: &mut syn::ImplItemFn) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_impl_item_fn_mut(self, item);
+    }
+
+    fn visit_foreign_item_fn_mut(&mut self, item: &mut syn::ForeignItemFn) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_foreign_item_fn_mut(self, item);
+    }
+
+    fn visit_foreign_item_static_mut(&mut self, item: &mut syn::ForeignItemStatic) {
+        Self::split(&mut item.attrs);
+        syn::visit_mut::visit_foreign_item_static_mut(self, item);
+    }
+}
+
+/// Escapes what rustdoc would take for links or HTML tags.
+#[cfg(feature = "bindgen")]
+fn escape_markdown(line: &str) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    for c in line.chars() {
+        if "[]<>".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Formats mentions of libui functions like `uiFreeText()` as code.
+#[cfg(feature = "bindgen")]
+fn code_spans(line: &str) -> String {
+    let words: Vec<String> = line
+        .split(' ')
+        .map(|word| {
+            let end = match word.find("()") {
+                Some(pos) => pos + 2,
+                None => return word.to_owned(),
+            };
+            let name = &word[..end - 2];
+            let is_function =
+                name.starts_with("ui") && name.chars().all(|c| c.is_ascii_alphanumeric());
+            if is_function {
+                format!("`{}`{}", &word[..end], &word[end..])
+            } else {
+                word.to_owned()
+            }
+        })
+        .collect();
+    words.join(" ")
+}
+
+/// ui.h declares its enums through `_UI_ENUM(name)`, which expands to an
+/// `unsigned int` typedef followed by an anonymous enum, so bindgen cannot
+/// tell which constants belong to which type. Redefining the macro to name
+/// the enum instead keeps the size and values, but lets bindgen generate
+/// proper types.
+#[cfg(feature = "bindgen")]
+fn named_enums(header: &str) -> String {
+    const ANONYMOUS: &str = "#define _UI_ENUM(s) typedef unsigned int s; enum";
+    const NAMED: &str = "#define _UI_ENUM(s) typedef enum s s; enum s";
+    if !header.contains(ANONYMOUS) {
+        crate::warn("ui.h does not define _UI_ENUM as expected, its enums are bound as integers");
+    }
+    header.replace(ANONYMOUS, NAMED)
+}
+
+/// The platform headers expect the toolkit's own headers to be included
+/// first. Apart from the Windows SDK those are not usable from C or are not
+/// around when cross-compiling, so the few toolkit types the headers use
+/// are declared opaque instead. Items of `ui.h` are left to the parent
+/// module.
+#[cfg(feature = "bindgen")]
+fn platform_builder(builder: bindgen::Builder, header: &Path, backend: &str) -> bindgen::Builder {
+    let prelude = match backend {
+        "unix" => {
+            "typedef struct _GtkWidget GtkWidget;\n\
+             typedef struct _GtkContainer GtkContainer;\n\
+             typedef struct _GtkWindow GtkWindow;\n\
+             typedef int gboolean;\n"
+        }
+        "darwin" => {
+            "typedef struct objc_object NSView;\n\
+             typedef struct objc_object NSControl;\n\
+             typedef struct objc_object NSWindow;\n\
+             typedef struct objc_object NSString;\n\
+             #if defined(__x86_64__) || defined(__i386__)\n\
+             typedef signed char BOOL;\n\
+             #else\n\
+             typedef _Bool BOOL;\n\
+             #endif\n\
+             typedef float NSLayoutPriority;\n\
+             typedef long NSLayoutConstraintOrientation;\n\
+             typedef unsigned long NSControlSize;\n"
+        }
+        _ => "#include <windows.h>\n",
+    };
+    let contents = format!(
+        "{}#include \"ui.h\"\n#include \"ui_{}.h\"\n",
+        prelude, backend
+    );
+    builder
+        .clang_arg(format!("-I{}", header.parent().unwrap().display()))
+        .header_contents("libui-sys-platform.h", &contents)
+        .allowlist_file(format!(".*[/\\\\]ui_{}\\.h", backend))
+        .blocklist_file(".*[/\\\\]ui\\.h")
+}
+
+#[cfg(not(feature = "bindgen"))]
+fn generate(_header: &Path, _backend: Option<&str>, _out_file: &Path) -> Result<(), BuildError> {
+    unreachable!("bindings generation requested without the bindgen feature")
+}
@@ -0,0 +1,202 @@
+//! Named aliases for the callback types in the generated bindings.
+//!
+//! bindgen spells out every callback as `Option<unsafe extern "C" fn(...)>`.
+//! Each of them gets an alias named after what it belongs to instead, e.g.
+//! `uiButtonOnClickedFn` for the callback parameter of `uiButtonOnClicked`
+//! and `uiAreaHandlerDrawFn` for the `Draw` member of `uiAreaHandler`, and
+//! the bindings are rewritten to use `Option<alias>`.
+//!
+//! The library structs of the `dlopen` feature, whose functions bindgen only
+//! exposes as `Result` fields, get a `missing_symbols` method on top, listing
+//! the functions that could not be loaded.
+
+use std::collections::HashSet;
+
+use syn::visit_mut::VisitMut;
+use syn::{
+    parse_quote, Fields, ForeignItemFn, GenericArgument, Ident, ImplItemFn, Item, ItemStruct,
+    PathArguments, Type, TypeBareFn,
+};
+
+/// Adds the aliases to the bindings in `file`.
+pub fn add_aliases(file: &mut syn::File) {
+    let mut aliases = Aliases::default();
+    aliases.visit_file_mut(file);
+    file.items.extend(aliases.items);
+    for (library, functions) in &aliases.loaded {
+        file.items.push(missing_symbols(library, functions));
+    }
+}
+
+#[derive(Default)]
+struct Aliases {
+    names: HashSet<String>,
+    items: Vec<Item>,
+    /// The `dlopen` library structs and the functions they load.
+    loaded: Vec<(Ident, Vec<Ident>)>,
+}
+
+impl Aliases {
+    /// Replaces the callback in `ty`, if it is one, by the alias `name`.
+    fn replace(&mut self, ty: &mut Type, name: String, owner: &str) {
+        let callback = match callback(ty) {
+            Some(callback) => callback,
+            None => return,
+        };
+        let alias: Ident = syn::parse_str(&name).unwrap();
+        if self.names.insert(name) {
+            let doc = format!(" Callback of `{}`.", owner);
+            self.items.push(parse_quote! {
+                #[doc = #doc]
+                pub type #alias = #callback;
+            });
+        }
+        *callback = parse_quote!(#alias);
+    }
+
+    /// Handles the parameters of the function `function`, given as names
+    /// and types.
+    fn replace_params<'a, I>(&mut self, function: &Ident, params: I)
+    where
+        I: IntoIterator<Item = (String, &'a mut Type)>,
+    {
+        let mut params: Vec<_> = params
+            .into_iter()
+            .filter(|(_, ty)| is_callback(ty))
+            .collect();
+        // Functions with a single callback, which is most of them, get a
+        // short name.
+        let single = params.len() == 1;
+        for (param, ty) in params.iter_mut() {
+            if single {
+                self.replace(ty, format!("{}Fn", function), &function.to_string());
+            } else {
+                let name = format!("{}{}Fn", function, capitalize(param));
+                self.replace(ty, name, &format!("{}({})", function, param));
+            }
+        }
+    }
+}
+
+impl VisitMut for Aliases {
+    fn visit_foreign_item_fn_mut(&mut self, item: &mut ForeignItemFn) {
+        let params = item.sig.inputs.iter_mut().filter_map(typed_param);
+        self.replace_params(&item.sig.ident.clone(), params);
+    }
+
+    /// Wrapper methods of the `dlopen` library struct.
+    fn visit_impl_item_fn_mut(&mut self, item: &mut ImplItemFn) {
+        let params = item.sig.inputs.iter_mut().filter_map(typed_param);
+        self.replace_params(&item.sig.ident.clone(), params);
+    }
+
+    fn visit_item_struct_mut(&mut self, item: &mut ItemStruct) {
+        let fields = match &mut item.fields {
+            Fields::Named(fields) => fields,
+            _ => return,
+        };
+        let mut loaded = Vec::new();
+        for field in fields.named.iter_mut() {
+            let ident = field.ident.clone().unwrap();
+            // Functions loaded by the `dlopen` library struct.
+            if let Some(function) = loaded_function(&mut field.ty) {
+                let params = function.inputs.iter_mut().filter_map(|arg| {
+                    let name = arg.name.as_ref()?.0.to_string();
+                    Some((name, &mut arg.ty))
+                });
+                self.replace_params(&ident, params);
+                loaded.push(ident);
+                continue;
+            }
+            let name = format!("{}{}Fn", item.ident, ident);
+            let owner = format!("{}::{}", item.ident, ident);
+            self.replace(&mut field.ty, name, &owner);
+        }
+        if !loaded.is_empty() {
+            self.loaded.push((item.ident.clone(), loaded));
+        }
+    }
+}
+
+/// Implements `missing_symbols` for the `dlopen` library struct `library`,
+/// which loads `functions`.
+fn missing_symbols(library: &Ident, functions: &[Ident]) -> Item {
+    let names = functions.iter().map(Ident::to_string);
+    parse_quote! {
+        impl #library {
+            /// Names of the functions that could not be loaded.
+            pub fn missing_symbols(&self) -> Vec<&'static str> {
+                let mut missing = Vec::new();
+                #(
+                    if self.#functions.is_err() {
+                        missing.push(#names);
+                    }
+                )*
+                missing
+            }
+        }
+    }
+}
+
+fn is_callback(ty: &Type) -> bool {
+    callback(&mut ty.clone()).is_some()
+}
+
+/// The function type in a callback type `Option<unsafe extern "C" fn(...)>`.
+fn callback(ty: &mut Type) -> Option<&mut Type> {
+    let arg = generic_arg(ty, "Option")?;
+    match arg {
+        Type::BareFn(_) => Some(arg),
+        _ => None,
+    }
+}
+
+/// The function type in a field `Result<unsafe extern "C" fn(...), _>` of
+/// the `dlopen` library struct.
+fn loaded_function(ty: &mut Type) -> Option<&mut TypeBareFn> {
+    match generic_arg(ty, "Result")? {
+        Type::BareFn(function) => Some(function),
+        _ => None,
+    }
+}
+
+/// The first type argument of `ty` if it is the generic type `name`.
+fn generic_arg<'a>(ty: &'a mut Type, name: &str) -> Option<&'a mut Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => path,
+        _ => return None,
+    };
+    let segment = path.path.segments.last_mut()?;
+    if segment.ident != name {
+        return None;
+    }
+    let args = match &mut segment.arguments {
+        PathArguments::AngleBracketed(args) => args,
+        _ => return None,
+    };
+    match args.args.first_mut()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Name and type of a function parameter that is not `self`.
+fn typed_param(arg: &mut syn::FnArg) -> Option<(String, &mut Type)> {
+    let arg = match arg {
+        syn::FnArg::Typed(arg) => arg,
+        syn::FnArg::Receiver(_) => return None,
+    };
+    let name = match &*arg.pat {
+        syn::Pat::Ident(pat) => pat.ident.to_string(),
+        _ => return None,
+    };
+    Some((name, &mut *arg.ty))
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,123 @@
+//! Cross-compilation support: meson cross files and bindgen clang arguments
+//! derived from Cargo's target configuration.
+
+use std::env;
+use std::fmt::Write;
+
+use crate::meson_syntax::{array, string};
+
+/// Whether Cargo builds for a target other than the host.
+pub fn is_cross() -> bool {
+    env::var("TARGET").unwrap() != env::var("HOST").unwrap()
+}
+
+/// Extra clang arguments that make bindgen parse headers for the target.
+#[cfg(feature = "bindgen")]
+pub fn clang_args() -> Vec<String> {
+    if !is_cross() {
+        return Vec::new();
+    }
+    let target = env::var("TARGET").unwrap();
+    let mut args = vec![format!("--target={}", clang_target(&target))];
+    if let Some(sysroot) = crate::env_var_os("PKG_CONFIG_SYSROOT_DIR") {
+        args.push(format!("--sysroot={}", sysroot.to_string_lossy()));
+    }
+    args
+}
+
+/// Contents of a meson cross file describing the target.
+pub fn meson_cross_file() -> String {
+    let target = env::var("TARGET").unwrap();
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let endian = env::var("CARGO_CFG_TARGET_ENDIAN").unwrap();
+    cross_file(&target, &arch, &os, &endian)
+}
+
+/// The meson cross file for `target`, whose architecture, OS and endianness
+/// are spelled like Cargo's `CARGO_CFG_TARGET_*` variables.
+pub fn cross_file(target: &str, arch: &str, os: &str, endian: &str) -> String {
+    let mut file = String::from("[binaries]\n");
+    let prefix = gnu_prefix(target);
+    let mut binaries = vec![
+        ("c", crate::target_env_var("CC"), format!("{}-gcc", prefix)),
+        (
+            "cpp",
+            crate::target_env_var("CXX"),
+            format!("{}-g++", prefix),
+        ),
+        ("ar", crate::target_env_var("AR"), format!("{}-ar", prefix)),
+    ];
+    if os == "windows" {
+        let windres = crate::target_env_var("WINDRES");
+        binaries.push(("windres", windres, format!("{}-windres", prefix)));
+    }
+    for (name, value, default) in binaries.iter() {
+        let value = value.clone().unwrap_or_else(|| default.clone());
+        writeln!(file, "{} = {}", name, array(value.split_whitespace())).unwrap();
+    }
+    let pkg_config = crate::env_var("PKG_CONFIG").unwrap_or_else(|| "pkg-config".to_owned());
+    writeln!(file, "pkgconfig = {}", array(pkg_config.split_whitespace())).unwrap();
+
+    file.push_str("\n[properties]\n");
+    if let Some(sysroot) = crate::env_var("PKG_CONFIG_SYSROOT_DIR") {
+        writeln!(file, "sys_root = {}", string(&sysroot)).unwrap();
+    }
+    if let Some(libdir) = crate::env_var("PKG_CONFIG_LIBDIR") {
+        writeln!(file, "pkg_config_libdir = {}", string(&libdir)).unwrap();
+    }
+
+    file.push_str("\n[host_machine]\n");
+    writeln!(file, "system = {}", string(meson_system(os))).unwrap();
+    writeln!(file, "cpu_family = {}", string(meson_cpu_family(arch))).unwrap();
+    writeln!(file, "cpu = {}", string(target.split('-').next().unwrap())).unwrap();
+    writeln!(file, "endian = {}", string(endian)).unwrap();
+    file
+}
+
+/// The GNU toolchain prefix conventionally used for the target, e.g.
+/// `aarch64-linux-gnu` for `aarch64-unknown-linux-gnu`.
+pub fn gnu_prefix(target: &str) -> String {
+    let (arch, rest) = target.split_at(target.find('-').unwrap_or(target.len()));
+    if target.ends_with("-windows-gnu") {
+        return format!("{}-w64-mingw32", arch);
+    }
+    // Little-endian 32-bit ARM toolchains go by plain `arm` whatever the
+    // architecture version, as in the cc crate: `armv7-unknown-linux-gnueabihf`
+    // is built with `arm-linux-gnueabihf-gcc`.
+    let arch =
+        if (arch.starts_with("arm") && !arch.starts_with("armeb")) || arch.starts_with("thumb") {
+            "arm"
+        } else {
+            arch
+        };
+    format!("{}{}", arch, rest)
+        .replace("-unknown-", "-")
+        .replace("-pc-", "-")
+}
+
+/// The target triple clang knows the target by.
+#[cfg(feature = "bindgen")]
+pub fn clang_target(target: &str) -> String {
+    // clang does not know about the extension letters Rust puts into RISC-V
+    // target names.
+    if target.starts_with("riscv64gc-") {
+        return target.replacen("riscv64gc", "riscv64", 1);
+    }
+    target.to_owned()
+}
+
+fn meson_system(os: &str) -> &str {
+    match os {
+        "macos" | "ios" => "darwin",
+        other => other,
+    }
+}
+
+fn meson_cpu_family(arch: &str) -> &str {
+    match arch {
+        "powerpc" => "ppc",
+        "powerpc64" => "ppc64",
+        other => other,
+    }
+}
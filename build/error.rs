@@ -0,0 +1,125 @@
+//! Errors reported by the build script.
+//!
+//! Every error carries an explanation of what went wrong and, where there is
+//! one, the fix, so that a failed build is actionable without reading the
+//! build script.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[derive(Debug)]
+pub enum BuildError {
+    /// The libui sources are not where they should be, which usually means
+    /// the git submodule was never initialised.
+    MissingSources { path: PathBuf },
+    /// An external program needed for the build is not installed.
+    MissingTool {
+        name: String,
+        hint: &'static str,
+        cause: io::Error,
+    },
+    /// A library needed for the build could not be found with pkg-config.
+    MissingPackage {
+        name: String,
+        hint: &'static str,
+        cause: String,
+    },
+    /// An external program ran but failed.
+    CommandFailed { command: String, output: String },
+    /// Anything else.
+    Other(String),
+}
+
+impl BuildError {
+    /// Turns a failure to spawn `cmd` into an error naming the program.
+    pub fn spawn(cmd: &Command, cause: io::Error) -> BuildError {
+        let name = cmd.get_program().to_string_lossy().into_owned();
+        if cause.kind() != io::ErrorKind::NotFound {
+            return BuildError::Other(format!("Unable to run {}: {}", name, cause));
+        }
+        let hint = tool_hint(&name);
+        BuildError::MissingTool { name, hint, cause }
+    }
+
+    /// Maps a pkg-config failure for package `name` to an error with `hint`
+    /// on how to install the package.
+    pub fn pkg_config(name: &str, hint: &'static str, err: pkg_config::Error) -> BuildError {
+        match err {
+            pkg_config::Error::Command { cause, .. } if cause.kind() == io::ErrorKind::NotFound => {
+                BuildError::MissingTool {
+                    name: "pkg-config".to_owned(),
+                    hint: tool_hint("pkg-config"),
+                    cause,
+                }
+            }
+            err => BuildError::MissingPackage {
+                name: name.to_owned(),
+                hint,
+                cause: err.to_string().trim().to_owned(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingSources { path } => write!(
+                f,
+                "The libui sources were not found at {}.\n\
+                 If you are building from a git checkout, run:\n\
+                 \n    git submodule update --init\n\
+                 \nor point LIBUI_SYS_SOURCE_DIR at a libui checkout, or set \
+                 LIBUI_SYS_USE_SYSTEM=1 to use an installed libui instead.",
+                path.display()
+            ),
+            BuildError::MissingTool { name, hint, cause } => {
+                write!(f, "`{}` was not found ({}).\n{}", name, cause, hint)
+            }
+            BuildError::MissingPackage { name, hint, cause } => write!(
+                f,
+                "The {} development files were not found.\n{}\n\npkg-config said: {}",
+                name, hint, cause
+            ),
+            BuildError::CommandFailed { command, output } => {
+                write!(f, "{} failed:\n{}", command, output)
+            }
+            BuildError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<io::Error> for BuildError {
+    fn from(err: io::Error) -> BuildError {
+        BuildError::Other(err.to_string())
+    }
+}
+
+impl From<String> for BuildError {
+    fn from(message: String) -> BuildError {
+        BuildError::Other(message)
+    }
+}
+
+/// Installation instructions for the external programs the build uses.
+fn tool_hint(name: &str) -> &'static str {
+    match name {
+        "meson" => {
+            "Install meson, e.g. with `pip3 install meson`. For GTK targets the \
+             `meson` feature can also be dropped to build libui with the cc crate."
+        }
+        "ninja" => {
+            "Install ninja, e.g. `apt install ninja-build` or `pip3 install ninja`. \
+             For GTK targets the `meson` feature can also be dropped to build libui \
+             with the cc crate."
+        }
+        "pkg-config" => "Install pkg-config, e.g. `apt install pkg-config`.",
+        _ => "Make sure it is installed and on the PATH.",
+    }
+}
+
+/// Installation instructions for GTK 3 development files.
+pub const GTK_HINT: &str = "Install the GTK 3 development package: `libgtk-3-dev` on \
+                            Debian and Ubuntu, `gtk3-devel` on Fedora, `gtk3` on Arch Linux.";
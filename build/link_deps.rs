@@ -0,0 +1,143 @@
+//! Link dependencies of a static libui.
+//!
+//! A static library does not record what it depends on, so the libraries
+//! libui's backend needs are taken from meson's view of the build and linked
+//! explicitly.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A single `cargo:rustc-link-*` directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkDirective {
+    /// A native library, linked as `-l<name>`.
+    Lib(String),
+    /// A macOS framework.
+    Framework(String),
+    /// A library search path.
+    SearchPath(PathBuf),
+}
+
+impl fmt::Display for LinkDirective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LinkDirective::Lib(name) => write!(f, "cargo:rustc-link-lib={}", name),
+            LinkDirective::Framework(name) => write!(f, "cargo:rustc-link-lib=framework={}", name),
+            LinkDirective::SearchPath(path) => {
+                write!(f, "cargo:rustc-link-search=native={}", path.display())
+            }
+        }
+    }
+}
+
+/// Extracts the link directives from the output of
+/// `meson introspect --dependencies` run on a configured build directory.
+pub fn from_introspection(json: &str) -> Result<Vec<LinkDirective>, String> {
+    let deps: serde_json::Value =
+        serde_json::from_str(json).map_err(|err| format!("invalid introspection JSON: {}", err))?;
+    let deps = deps
+        .as_array()
+        .ok_or("introspection JSON is not a list of dependencies")?;
+
+    let mut args = Vec::new();
+    for dep in deps {
+        let link_args = match dep.get("link_args") {
+            Some(link_args) => link_args,
+            None => continue,
+        };
+        let link_args = link_args
+            .as_array()
+            .ok_or("dependency link_args is not a list")?;
+        for arg in link_args {
+            let arg = arg
+                .as_str()
+                .ok_or("dependency link argument is not a string")?;
+            args.push(arg.to_owned());
+        }
+    }
+    Ok(from_link_args(&args))
+}
+
+/// The link arguments of libui's unix backend: GTK in the `link_paths` and
+/// `libs` pkg-config found for it, plus libm and libdl.
+pub fn gtk_link_args(link_paths: &[PathBuf], libs: &[String]) -> Vec<String> {
+    let mut args: Vec<String> = link_paths
+        .iter()
+        .map(|path| format!("-L{}", path.display()))
+        .collect();
+    args.extend(libs.iter().map(|lib| format!("-l{}", lib)));
+    args.extend(["-lm".to_owned(), "-ldl".to_owned()]);
+    args
+}
+
+/// Translates compiler driver link arguments into link directives, dropping
+/// duplicates and arguments that have no Cargo equivalent.
+pub fn from_link_args<S: AsRef<str>>(args: &[S]) -> Vec<LinkDirective> {
+    let mut directives = Vec::new();
+    let mut push = |directive| {
+        if !directives.contains(&directive) {
+            directives.push(directive);
+        }
+    };
+
+    let mut args = args.iter().map(AsRef::as_ref);
+    while let Some(arg) = args.next() {
+        if arg == "-framework" {
+            if let Some(name) = args.next() {
+                push(LinkDirective::Framework(name.to_owned()));
+            }
+        } else if let Some(name) = arg.strip_prefix("-l") {
+            push(LinkDirective::Lib(name.to_owned()));
+        } else if let Some(path) = arg.strip_prefix("-L") {
+            push(LinkDirective::SearchPath(PathBuf::from(path)));
+        } else if arg == "-pthread" {
+            push(LinkDirective::Lib("pthread".to_owned()));
+        } else if !arg.starts_with('-') {
+            if let Some((dir, name)) = library_file(Path::new(arg)) {
+                if let Some(dir) = dir {
+                    push(LinkDirective::SearchPath(dir));
+                }
+                push(LinkDirective::Lib(name));
+            }
+        }
+    }
+    directives
+}
+
+/// Recognises library file arguments such as `user32.lib` (MSVC) or
+/// `/usr/lib/libm.so`, returning their directory and library name.
+fn library_file(path: &Path) -> Option<(Option<PathBuf>, String)> {
+    let stem = path.file_stem()?.to_str()?;
+    let name = match path.extension()?.to_str()? {
+        "lib" => stem,
+        "a" | "so" | "dylib" | "tbd" => stem.strip_prefix("lib").unwrap_or(stem),
+        _ => return None,
+    };
+    let dir = path
+        .parent()
+        .filter(|dir| *dir != Path::new("") && !is_linker_default(dir))
+        .map(Path::to_owned);
+    Some((dir, name.to_owned()))
+}
+
+/// Whether the linker searches `dir` anyway, like `/usr/lib` or the
+/// multiarch `/usr/lib/x86_64-linux-gnu`. Passing these on as search paths
+/// would put host libraries ahead of those of a sysroot.
+fn is_linker_default(dir: &Path) -> bool {
+    const DEFAULT_DIRS: &[&str] = &[
+        "/lib",
+        "/lib32",
+        "/lib64",
+        "/usr/lib",
+        "/usr/lib32",
+        "/usr/lib64",
+    ];
+    let is_default = |dir: &Path| DEFAULT_DIRS.iter().any(|default| dir == Path::new(default));
+    if is_default(dir) {
+        return true;
+    }
+    match (dir.file_name().and_then(|name| name.to_str()), dir.parent()) {
+        (Some(name), Some(parent)) => name.contains("-linux-") && is_default(parent),
+        _ => false,
+    }
+}
@@ -0,0 +1,416 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+mod bindings;
+#[cfg(feature = "bindgen")]
+mod callbacks;
+mod cross;
+mod error;
+mod link_deps;
+mod meson;
+mod meson_syntax;
+mod prebuilt;
+mod rpath;
+mod symbol_table;
+mod symbols;
+mod system;
+#[cfg(feature = "vendored-cc")]
+mod vendored;
+
+use error::BuildError;
+
+/// Target operating systems served by the `unix` (GTK) backend of libui.
+const UNIX_BACKEND_OSES: &[&str] = &["linux", "freebsd", "dragonfly", "netbsd", "openbsd"];
+
+/// The oldest GTK 3 libui supports.
+pub const GTK_MIN_VERSION: &str = "3.10.0";
+
+/// A libui built from source by this script.
+pub struct BundledLibrary {
+    /// Directory containing the library.
+    pub lib_dir: PathBuf,
+    /// What a static libui has to be linked with.
+    pub dependencies: Vec<link_deps::LinkDirective>,
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("\nerror: failed to build libui-sys\n\n{}\n", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), BuildError> {
+    let out_path = PathBuf::from(env::var_os("OUT_DIR").expect("Unable to read OUT_DIR env var"));
+
+    // Prepare linking options.
+    let static_linking = env_var_os("LIBUI_SYS_STATIC_BUILD").is_some()
+        || env::var_os("CARGO_FEATURE_STATIC").is_some();
+    if static_linking && is_dlopen() {
+        return Err(BuildError::Other(
+            "The `dlopen` feature loads a shared libui at runtime and cannot be combined \
+             with static linking."
+                .to_owned(),
+        ));
+    }
+
+    // Use prebuilt artifacts or a system-wide libui if requested, otherwise
+    // build the bundled one.
+    let prebuilt = prebuilt::find(static_linking)?;
+    let system = match prebuilt {
+        Some(_) => None,
+        None => system::find(static_linking)?,
+    };
+    let bundled = prebuilt.is_none() && system.is_none();
+    let src = source_dir()?;
+    let header = match (&prebuilt, &system) {
+        (Some(lib), _) => lib.include_dir.join("ui.h"),
+        (None, Some(lib)) => lib.header.clone(),
+        (None, None) => src.join("ui.h"),
+    };
+    if bundled && !header.exists() {
+        return Err(BuildError::MissingSources { path: src });
+    }
+    // Loading libui at runtime only needs the bindings, so the bundled libui
+    // is neither built nor linked then.
+    let build = bundled && !is_dlopen();
+
+    // Rerun only when an input of the build changes, rather than on every
+    // change in the package.
+    println!("cargo:rerun-if-changed={}", header.display());
+    if build {
+        println!("cargo:rerun-if-changed={}", src.display());
+    }
+    println!("cargo:rerun-if-changed=shared_resources.rc");
+    println!("cargo:rerun-if-changed=shared.manifest");
+
+    // Generate or copy bindings.
+    let bindings = bindings::write(&header, &out_path)?;
+
+    let lib_dir = match (prebuilt, system) {
+        (Some(lib), _) => {
+            if !is_dlopen() {
+                link_libui(&lib.lib_dir, static_linking);
+                for dependency in prebuilt::dependencies(static_linking)? {
+                    println!("{}", dependency);
+                }
+            }
+            Some(lib.lib_dir)
+        }
+        (None, Some(lib)) => lib.lib_dir,
+        (None, None) if build => Some(build_bundled(&src, &out_path, static_linking)?),
+        (None, None) => None,
+    };
+
+    // List the functions the library implements.
+    symbols::write(lib_dir.as_deref(), static_linking, &bindings, &out_path)?;
+
+    // Let the crate itself tell the libui-ng only items apart.
+    println!("cargo:rustc-check-cfg=cfg(libui_ng)");
+    if is_ng() {
+        println!("cargo:rustc-cfg=libui_ng");
+    }
+
+    // Export metadata for the build scripts of dependent crates, which
+    // receive it as DEP_UI_INCLUDE, DEP_UI_ROOT, DEP_UI_LIB_DIR,
+    // DEP_UI_STATIC, DEP_UI_NG and DEP_UI_BINDINGS. DEP_UI_NG is how
+    // dependants can set a `libui_ng` cfg of their own. A shared bundled
+    // libui also exports DEP_UI_RPATH, see rpath.rs.
+    println!("cargo:include={}", header.parent().unwrap().display());
+    if build {
+        println!("cargo:root={}", out_path.display());
+    }
+    if let Some(lib_dir) = lib_dir {
+        println!("cargo:lib_dir={}", lib_dir.display());
+    }
+    println!("cargo:static={}", if static_linking { 1 } else { 0 });
+    println!("cargo:ng={}", if is_ng() { 1 } else { 0 });
+    println!("cargo:bindings={}", out_path.join("bindings.rs").display());
+
+    // Embed manifests for shared library.
+    if !static_linking {
+        embed_resource::compile("shared_resources.rc");
+    }
+    Ok(())
+}
+
+/// Reads an environment variable that affects the build, making Cargo rerun
+/// the build script when it changes.
+pub fn env_var_os(name: &str) -> Option<OsString> {
+    println!("cargo:rerun-if-env-changed={}", name);
+    env::var_os(name)
+}
+
+/// Like `env_var_os`, ignoring values that are not valid unicode.
+pub fn env_var(name: &str) -> Option<String> {
+    env_var_os(name).and_then(|value| value.into_string().ok())
+}
+
+/// Looks up a per-target variable the way the `cc` crate does, trying e.g.
+/// `CFLAGS_<target>`, `CFLAGS_<target_with_underscores>`, `TARGET_CFLAGS`
+/// (`HOST_CFLAGS` for native builds) and finally `CFLAGS`.
+pub fn target_env_var(name: &str) -> Option<String> {
+    target_env_var_names(name)
+        .iter()
+        .find_map(|var| env_var(var))
+}
+
+/// Collects compiler flags from all the variables `target_env_var` looks
+/// `name` up in, least specific first, like the cc crate does, so that e.g.
+/// `CFLAGS_<target>` can override what `CFLAGS` sets. `None` if none of the
+/// variables is set.
+pub fn target_env_flags(name: &str) -> Option<Vec<String>> {
+    let mut flags = None;
+    for var in target_env_var_names(name).iter().rev() {
+        if let Some(value) = env_var(var) {
+            flags
+                .get_or_insert_with(Vec::new)
+                .extend(value.split_whitespace().map(str::to_owned));
+        }
+    }
+    flags
+}
+
+/// The variables `target_env_var` looks `name` up in, most specific first.
+pub fn target_env_var_names(name: &str) -> [String; 4] {
+    let target = env::var("TARGET").unwrap();
+    let kind = if cross::is_cross() { "TARGET" } else { "HOST" };
+    [
+        format!("{}_{}", name, target),
+        format!("{}_{}", name, target.replace('-', "_")),
+        format!("{}_{}", kind, name),
+        name.to_owned(),
+    ]
+}
+
+/// Emits a non-fatal diagnostic.
+pub fn warn(message: &str) {
+    println!("cargo:warning={}", message.replace('\n', " "));
+}
+
+/// Whether libui is loaded at runtime rather than linked.
+pub fn is_dlopen() -> bool {
+    env::var_os("CARGO_FEATURE_DLOPEN").is_some()
+}
+
+/// Whether libui-ng is built and bound instead of andlabs/libui.
+pub fn is_ng() -> bool {
+    env::var_os("CARGO_FEATURE_NG").is_some()
+}
+
+/// The libui sources to build, `LIBUI_SYS_SOURCE_DIR` or the bundled
+/// submodule.
+pub fn source_dir() -> Result<PathBuf, BuildError> {
+    Ok(match env_dir("LIBUI_SYS_SOURCE_DIR")? {
+        Some(dir) => dir,
+        None if is_ng() => manifest_dir().join("libui-ng"),
+        None => manifest_dir().join("libui"),
+    })
+}
+
+/// Reads a directory from the environment variable `name`. The path has to
+/// be absolute, as a relative one would be taken relative to the directory
+/// Cargo runs the build script in, which for a dependency is its copy in
+/// the Cargo registry rather than the user's workspace.
+pub fn env_dir(name: &str) -> Result<Option<PathBuf>, BuildError> {
+    let dir = match env_var_os(name) {
+        Some(dir) => PathBuf::from(dir),
+        None => return Ok(None),
+    };
+    if dir.is_relative() {
+        return Err(BuildError::Other(format!(
+            "{} is set to the relative path {}, but it has to be an absolute path.",
+            name,
+            dir.display()
+        )));
+    }
+    Ok(Some(dir))
+}
+
+/// Builds and links the libui sources at `src`, returning the directory
+/// containing the library.
+fn build_bundled(src: &Path, out_path: &Path, static_linking: bool) -> Result<PathBuf, BuildError> {
+    // Determine target properties.
+    let target = env::var("TARGET").unwrap();
+    let msvc = target.contains("msvc");
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+
+    if target_os == "windows" {
+        // Meson reads the resource compiler from WINDRES itself, which the
+        // detection for MSVC fills in unless DO_NOT_DETECT_WINDRES is set.
+        env_var_os("WINDRES");
+        env_var_os("DO_NOT_DETECT_WINDRES");
+    }
+    if msvc && !static_linking {
+        // Detect windres executable location and populate the env var for meson.
+        detect_windres_msvc()?;
+    }
+
+    // Check for everything the build needs up front, so that a missing
+    // dependency is reported as such rather than as a compiler error.
+    let vendored = use_vendored_build(&target_os);
+    if !vendored {
+        meson::check_tools()?;
+    } else if env_var_os("LIBUI_SYS_MESON_ARGS").is_some() {
+        warn("LIBUI_SYS_MESON_ARGS is ignored since libui is built with the cc crate, enable the `meson` feature to use it");
+    }
+    let gtk = if is_unix_backend(&target_os) {
+        Some(probe_gtk()?)
+    } else {
+        None
+    };
+
+    // Build library.
+    let build = if vendored {
+        // The vendored build only serves the unix backend, so GTK was found
+        // above.
+        let gtk = gtk.as_ref().unwrap();
+        build_vendored(src, &out_path.join("build-cc"), static_linking, gtk)?
+    } else {
+        meson::build(src, out_path, static_linking)?
+    };
+    let build_out_path = build.lib_dir.as_path();
+
+    // Link library.
+    if msvc && static_linking {
+        // See https://github.com/mesonbuild/meson/issues/1412
+        // With MSVC Rust searches for "<name-without-lib>.lib", but meson
+        // generates "<name-with-lib>.a". Make them play together.
+        fs::copy(
+            build_out_path.join("libui.a"),
+            build_out_path.join("ui.lib"),
+        )
+        .map_err(|err| format!("Unable to copy libui.a to ui.lib: {}", err))?;
+    }
+    link_libui(build_out_path, static_linking);
+    if !static_linking {
+        if let Some(rpath) = rpath::configure(build_out_path, out_path)? {
+            println!("cargo:rpath={}", rpath);
+        }
+    }
+
+    // A static libui needs its own dependencies linked after it.
+    for dependency in &build.dependencies {
+        println!("{}", dependency);
+    }
+
+    Ok(build.lib_dir)
+}
+
+/// Looks up GTK 3 with pkg-config, without emitting any link directives.
+pub fn probe_gtk() -> Result<pkg_config::Library, BuildError> {
+    pkg_config::Config::new()
+        .atleast_version(GTK_MIN_VERSION)
+        .cargo_metadata(false)
+        .probe("gtk+-3.0")
+        .map_err(|err| BuildError::pkg_config("GTK 3", error::GTK_HINT, err))
+}
+
+/// Links the libui in `lib_dir`.
+fn link_libui(lib_dir: &Path, static_linking: bool) {
+    let msvc = env::var("TARGET").unwrap().contains("msvc");
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+
+    // Link the versioned soname as is, as there is no unversioned libui.so
+    // for `-lui` to find.
+    println!("cargo:rustc-check-cfg=cfg(libui_versioned_soname)");
+    if !static_linking && lib_dir.join("libui.so.0").exists() {
+        println!("cargo:rustc-link-lib=dylib:+verbatim=libui.so.0");
+        // Lets the tests check the soname ends up in the binaries.
+        println!("cargo:rustc-cfg=libui_versioned_soname");
+        return;
+    }
+    println!(
+        "cargo:rustc-link-lib={}={}",
+        if static_linking { "static" } else { "dylib" },
+        if msvc && !static_linking {
+            "libui"
+        } else {
+            "ui"
+        }
+    );
+}
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(
+        env::var_os("CARGO_MANIFEST_DIR").expect("Unable to read CARGO_MANIFEST_DIR env var"),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn detect_windres_msvc() -> Result<(), BuildError> {
+    if env_var_os("DO_NOT_DETECT_WINDRES").is_some() || env_var_os("WINDRES").is_some() {
+        return Ok(());
+    }
+
+    let sdk_info = find_winsdk::SdkInfo::find(find_winsdk::SdkVersion::Any)
+        .map_err(|err| format!("Unable to look up the Windows SDK: {}", err))?;
+    let sdk_info = match sdk_info {
+        Some(sdk_info) => sdk_info,
+        None => {
+            warn("Unable to find the Windows SDK, set WINDRES to the path of rc.exe if meson can't find it");
+            return Ok(());
+        }
+    };
+
+    let sdk_folder = sdk_info.installation_folder();
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let windres_path = match arch.as_str() {
+        "x86_64" => sdk_folder.join("bin/x64/rc.exe"),
+        "x86" => sdk_folder.join("bin/x86/rc.exe"),
+        other => {
+            return Err(BuildError::Other(format!(
+                "There is no rc.exe for the target architecture {} in the Windows SDK.\n\
+                 Set WINDRES to the path of a resource compiler to use.",
+                other
+            )))
+        }
+    };
+
+    // double-quote path to escape spaces
+    std::env::set_var("WINDRES", format!(r#""{}""#, windres_path.display()));
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn detect_windres_msvc() -> Result<(), BuildError> {
+    // noop
+    Ok(())
+}
+
+/// Whether libui's `unix` (GTK) backend is used for `target_os`.
+fn is_unix_backend(target_os: &str) -> bool {
+    UNIX_BACKEND_OSES.contains(&target_os)
+}
+
+/// The `cc` based build is used when enabled and the target backend is
+/// supported by it, unless meson is requested explicitly.
+fn use_vendored_build(target_os: &str) -> bool {
+    if env::var_os("CARGO_FEATURE_MESON").is_some() {
+        return false;
+    }
+    cfg!(feature = "vendored-cc") && is_unix_backend(target_os)
+}
+
+#[cfg(feature = "vendored-cc")]
+fn build_vendored(
+    src: &Path,
+    dir: &Path,
+    static_linking: bool,
+    gtk: &pkg_config::Library,
+) -> Result<BundledLibrary, BuildError> {
+    vendored::build(src, dir, static_linking, gtk)
+}
+
+#[cfg(not(feature = "vendored-cc"))]
+fn build_vendored(
+    _src: &Path,
+    _dir: &Path,
+    _static_linking: bool,
+    _gtk: &pkg_config::Library,
+) -> Result<BundledLibrary, BuildError> {
+    unreachable!("vendored build requested without the vendored-cc feature")
+}
@@ -0,0 +1,271 @@
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::error::BuildError;
+use crate::link_deps;
+use crate::meson_syntax::array;
+use crate::{cross, BundledLibrary};
+
+/// Meson build types, see `meson setup --help`.
+const BUILD_TYPES: &[&str] = &[
+    "plain",
+    "debug",
+    "debugoptimized",
+    "release",
+    "minsize",
+    "custom",
+];
+
+/// File in the build directory recording the options it was configured with.
+const STAMP_FILE: &str = "libui-sys-options.stamp";
+
+/// Builds the libui sources at `src` with meson in a subdirectory of
+/// `out_path`.
+pub fn build(
+    src: &Path,
+    out_path: &Path,
+    static_linking: bool,
+) -> Result<BundledLibrary, BuildError> {
+    let buildtype = buildtype()?;
+
+    let mut options = vec![
+        format!(
+            "--default-library={}",
+            if static_linking { "static" } else { "shared" }
+        ),
+        format!("--buildtype={}", buildtype),
+        "--backend=ninja".to_owned(),
+    ];
+    if crate::is_ng() {
+        // Only the library itself is needed.
+        options.push("-Dtests=false".to_owned());
+        options.push("-Dexamples=false".to_owned());
+    }
+    if env::var("TARGET").unwrap().contains("msvc") {
+        // Debug build types would otherwise pick the debug CRT, which Rust
+        // never links against.
+        options.push(format!(
+            "-Db_vscrt={}",
+            if crt_static() { "mt" } else { "md" }
+        ));
+    }
+    // Flags from the environment, combined the way the cc crate does. Meson
+    // reads CFLAGS and CXXFLAGS itself on native builds, but only as the
+    // default of these options, which the ones given here replace, so the
+    // flags are not applied twice.
+    for &(option, var) in &[("c_args", "CFLAGS"), ("cpp_args", "CXXFLAGS")] {
+        if let Some(flags) = crate::target_env_flags(var) {
+            options.push(format!(
+                "-D{}={}",
+                option,
+                array(flags.iter().map(String::as_str))
+            ));
+        }
+    }
+    options.extend(extra_args()?);
+
+    // Extra arguments and flags are part of the fingerprint too, so changing
+    // them reconfigures the build directory. So does switching to another
+    // source tree, which meson refuses to reuse a build directory for.
+    let mut fingerprint = format!("{}\n{}", src.display(), options.join("\n"));
+    if cross::is_cross() {
+        let path = out_path.join("cross.ini");
+        let contents = cross::meson_cross_file();
+        fs::write(&path, &contents)?;
+        options.push(format!("--cross-file={}", path.display()));
+        fingerprint.push_str("\n\n");
+        fingerprint.push_str(&contents);
+    }
+
+    // Every build type gets its own directory, so switching between Cargo
+    // profiles does not reuse a directory configured for another one.
+    let build_path = out_path.join(format!("build-{}", buildtype));
+    run_meson(src, &build_path, &options, &fingerprint)?;
+
+    let dependencies = if static_linking {
+        let json = run_command(
+            &build_path,
+            "meson",
+            &[
+                OsStr::new("introspect"),
+                OsStr::new("--dependencies"),
+                OsStr::new("."),
+            ],
+        )?;
+        link_deps::from_introspection(&json)
+            .map_err(|err| format!("Unable to read libui dependencies from meson: {}", err))?
+    } else {
+        Vec::new()
+    };
+
+    Ok(BundledLibrary {
+        lib_dir: build_path.join("meson-out"),
+        dependencies,
+    })
+}
+
+/// Makes sure meson and ninja can be run.
+pub fn check_tools() -> Result<(), BuildError> {
+    run_command(".", "meson", &[OsStr::new("--version")])?;
+    run_command(".", ninja(), &[OsStr::new("--version")])?;
+    Ok(())
+}
+
+/// The ninja implementation to use, `NINJA` allows picking e.g. `samu`.
+fn ninja() -> OsString {
+    crate::env_var_os("NINJA").unwrap_or_else(|| OsString::from("ninja"))
+}
+
+/// The meson build type given with `LIBUI_SYS_BUILDTYPE`, if any.
+pub fn buildtype_override() -> Result<Option<String>, BuildError> {
+    match crate::env_var("LIBUI_SYS_BUILDTYPE") {
+        Some(buildtype) if !BUILD_TYPES.contains(&buildtype.as_str()) => Err(format!(
+            "Invalid LIBUI_SYS_BUILDTYPE {:?}, expected one of: {}",
+            buildtype,
+            BUILD_TYPES.join(", ")
+        )
+        .into()),
+        buildtype => Ok(buildtype),
+    }
+}
+
+/// The meson build type matching the Cargo profile, unless overridden with
+/// `LIBUI_SYS_BUILDTYPE`.
+fn buildtype() -> Result<String, BuildError> {
+    if let Some(buildtype) = buildtype_override()? {
+        return Ok(buildtype);
+    }
+
+    let opt_level = env::var("OPT_LEVEL").unwrap_or_default();
+    let debug = match env::var("DEBUG") {
+        Ok(ref v) => v != "false" && v != "0" && v != "none",
+        Err(_) => env::var("PROFILE").as_deref() == Ok("debug"),
+    };
+    let buildtype = match opt_level.as_str() {
+        "0" => "debug",
+        "s" | "z" => "minsize",
+        _ if debug => "debugoptimized",
+        _ => "release",
+    };
+    Ok(buildtype.to_owned())
+}
+
+/// Additional meson arguments from `LIBUI_SYS_MESON_ARGS`, split like a
+/// shell would, e.g. `-Db_sanitize=address -Db_lto=true`.
+fn extra_args() -> Result<Vec<String>, BuildError> {
+    match crate::env_var("LIBUI_SYS_MESON_ARGS") {
+        Some(args) => shlex::split(&args).ok_or_else(|| {
+            BuildError::Other(format!("Unable to parse LIBUI_SYS_MESON_ARGS: {:?}", args))
+        }),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn crt_static() -> bool {
+    env::var("CARGO_CFG_TARGET_FEATURE")
+        .unwrap_or_default()
+        .split(',')
+        .any(|feature| feature == "crt-static")
+}
+
+/// Configures `dir` with `options` unless it already is, and builds it.
+///
+/// `fingerprint` identifies the configuration and is recorded in the build
+/// directory. A directory configured with a different fingerprint is wiped
+/// and configured from scratch, since meson can't switch options like the
+/// default library type in place reliably.
+fn run_meson<L, D>(lib: L, dir: D, options: &[String], fingerprint: &str) -> Result<(), BuildError>
+where
+    L: AsRef<OsStr>,
+    D: AsRef<OsStr>,
+{
+    let stamp_path = Path::new(dir.as_ref()).join(STAMP_FILE);
+    if is_configured(dir.as_ref())
+        && fs::read_to_string(&stamp_path).ok().as_deref() != Some(fingerprint)
+    {
+        fs::remove_dir_all(dir.as_ref())?;
+    }
+
+    if !is_configured(dir.as_ref()) {
+        let mut args = vec![OsStr::new("."), dir.as_ref()];
+        args.extend(options.iter().map(OsStr::new));
+        run_command(lib, "meson", &args)?;
+        fs::write(&stamp_path, fingerprint)?;
+    }
+    run_ninja(dir.as_ref())
+}
+
+/// Builds the configured directory, running as many jobs as Cargo allows.
+fn run_ninja(dir: &OsStr) -> Result<(), BuildError> {
+    let mut cmd = Command::new(ninja());
+    cmd.current_dir(dir);
+    if let Ok(jobs) = env::var("NUM_JOBS") {
+        cmd.arg("-j").arg(jobs);
+    }
+    // Jobserver-aware ninja versions share Cargo's job tokens through this.
+    if let Some(makeflags) = env::var_os("CARGO_MAKEFLAGS") {
+        cmd.env("MAKEFLAGS", makeflags);
+    }
+
+    // Stream the progress instead of buffering it, but keep it out of the
+    // build script's stdout, which Cargo parses for directives.
+    cmd.stdout(Stdio::piped());
+    let mut child = cmd.spawn().map_err(|err| BuildError::spawn(&cmd, err))?;
+    io::copy(&mut child.stdout.take().unwrap(), &mut io::stderr())?;
+    if !child.wait()?.success() {
+        return Err(BuildError::CommandFailed {
+            command: describe(&cmd),
+            output: "See the ninja output above for details.".to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Runs a command to completion and returns its standard output.
+fn run_command<D, N>(dir: D, name: N, args: &[&OsStr]) -> Result<String, BuildError>
+where
+    D: AsRef<OsStr>,
+    N: AsRef<OsStr>,
+{
+    let mut cmd = Command::new(name);
+    cmd.current_dir(dir.as_ref());
+    if !args.is_empty() {
+        cmd.args(args);
+    }
+    let out = cmd.output().map_err(|err| BuildError::spawn(&cmd, err))?;
+    if !out.status.success() {
+        // This does not work great on Windows with non-ascii output,
+        // but for now it"s good enough.
+        let errtext = String::from_utf8_lossy(&out.stderr);
+        let outtext = String::from_utf8_lossy(&out.stdout);
+        return Err(BuildError::CommandFailed {
+            command: describe(&cmd),
+            output: format!("{}\n{}", outtext, errtext),
+        });
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// A readable rendition of a command line.
+fn describe(cmd: &Command) -> String {
+    let mut description = format!("`{}", cmd.get_program().to_string_lossy());
+    for arg in cmd.get_args() {
+        description.push(' ');
+        description.push_str(&arg.to_string_lossy());
+    }
+    description.push('`');
+    description
+}
+
+fn is_configured<S>(dir: S) -> bool
+where
+    S: AsRef<OsStr>,
+{
+    let mut path = PathBuf::from(dir.as_ref());
+    path.push("build.ninja");
+    path.exists()
+}
@@ -0,0 +1,13 @@
+//! Literals of meson's own language, for options and cross files.
+
+/// Meson string literal.
+pub fn string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Meson array literal. Used for list options too, since the plain
+/// comma-separated form would split flags like `-Wl,-z,relro`.
+pub fn array<'a, I: IntoIterator<Item = &'a str>>(items: I) -> String {
+    let items: Vec<String> = items.into_iter().map(string).collect();
+    format!("[{}]", items.join(", "))
+}
@@ -0,0 +1,115 @@
+//! Support for prebuilt libui artifacts given by `LIBUI_SYS_LIB_DIR` and
+//! `LIBUI_SYS_INCLUDE_DIR`, which skips compiling libui altogether.
+
+use std::env;
+use std::path::PathBuf;
+
+use crate::error::BuildError;
+use crate::link_deps::{self, LinkDirective};
+
+/// System libraries the windows backend links against, which a static libui
+/// does not record by itself. There is no meson build to ask for a prebuilt
+/// libui, so this has to track the libraries in libui's
+/// `windows/meson.build`.
+const WINDOWS_LIBS: &[&str] = &[
+    "user32",
+    "kernel32",
+    "gdi32",
+    "comctl32",
+    "uxtheme",
+    "msimg32",
+    "comdlg32",
+    "d2d1",
+    "dwrite",
+    "ole32",
+    "oleaut32",
+    "oleacc",
+    "uuid",
+    "windowscodecs",
+];
+
+/// Frameworks the darwin backend links against.
+const DARWIN_FRAMEWORKS: &[&str] = &["Foundation", "AppKit"];
+
+pub struct PrebuiltLibrary {
+    /// Directory containing `ui.h`.
+    pub include_dir: PathBuf,
+    /// Directory containing the library.
+    pub lib_dir: PathBuf,
+}
+
+/// Looks up prebuilt artifacts if they were configured.
+pub fn find(static_linking: bool) -> Result<Option<PrebuiltLibrary>, BuildError> {
+    let lib_dir = crate::env_dir("LIBUI_SYS_LIB_DIR")?;
+    let include_dir = crate::env_dir("LIBUI_SYS_INCLUDE_DIR")?;
+    let (lib_dir, include_dir) = match (lib_dir, include_dir) {
+        (Some(lib_dir), Some(include_dir)) => (lib_dir, include_dir),
+        (None, None) => return Ok(None),
+        _ => {
+            return Err(BuildError::Other(
+                "LIBUI_SYS_LIB_DIR and LIBUI_SYS_INCLUDE_DIR have to be set together.".to_owned(),
+            ))
+        }
+    };
+
+    if !include_dir.join("ui.h").exists() {
+        return Err(BuildError::Other(format!(
+            "LIBUI_SYS_INCLUDE_DIR is set to {}, but there is no ui.h in it.",
+            include_dir.display()
+        )));
+    }
+    let candidates = library_files(static_linking);
+    if !candidates.iter().any(|file| lib_dir.join(file).exists()) {
+        return Err(BuildError::Other(format!(
+            "LIBUI_SYS_LIB_DIR is set to {}, but none of {} is in it.",
+            lib_dir.display(),
+            candidates.join(", ")
+        )));
+    }
+
+    Ok(Some(PrebuiltLibrary {
+        include_dir,
+        lib_dir,
+    }))
+}
+
+/// What a static prebuilt libui has to be linked with. As there is no build
+/// to ask, this is what libui's meson.build uses for each backend.
+pub fn dependencies(static_linking: bool) -> Result<Vec<LinkDirective>, BuildError> {
+    if !static_linking {
+        return Ok(Vec::new());
+    }
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let args: Vec<String> = if crate::is_unix_backend(&target_os) {
+        let gtk = crate::probe_gtk()?;
+        link_deps::gtk_link_args(&gtk.link_paths, &gtk.libs)
+    } else if target_os == "windows" {
+        WINDOWS_LIBS
+            .iter()
+            .map(|lib| format!("-l{}", lib))
+            .collect()
+    } else if target_os == "macos" {
+        DARWIN_FRAMEWORKS
+            .iter()
+            .flat_map(|framework| vec!["-framework".to_owned(), framework.to_string()])
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Ok(link_deps::from_link_args(&args))
+}
+
+/// File names the library may have in `LIBUI_SYS_LIB_DIR`, or in the
+/// build directory of the bundled one.
+pub fn library_files(static_linking: bool) -> Vec<&'static str> {
+    let target = env::var("TARGET").unwrap();
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    match (static_linking, target.contains("msvc")) {
+        (true, true) => vec!["ui.lib"],
+        (true, false) => vec!["libui.a"],
+        (false, true) => vec!["libui.lib"],
+        (false, false) if target_os == "windows" => vec!["libui.dll.a", "libui.dll"],
+        (false, false) if target_os == "macos" => vec!["libui.dylib", "libui.A.dylib"],
+        (false, false) => vec!["libui.so", "libui.so.0"],
+    }
+}
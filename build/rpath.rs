@@ -0,0 +1,91 @@
+//! Runtime search paths for a shared bundled libui, so that binaries find
+//! it without `LD_LIBRARY_PATH`.
+//!
+//! `LIBUI_SYS_RPATH` selects what is embedded:
+//!
+//! * `absolute` (the default): the directory libui was built in, which is
+//!   right for development.
+//! * `origin`: the directory of the binary (`$ORIGIN`, `@loader_path` on
+//!   macOS), for deployment. The library is copied next to the artifacts in
+//!   `target/<profile>/` and `target/<profile>/deps/` as well.
+//! * `none`: nothing.
+//!
+//! Cargo only applies link arguments to the targets of this package, so the
+//! rpath is also exported as `cargo:rpath` for dependants to pass on with
+//! `cargo:rustc-link-arg=-Wl,-rpath,$DEP_UI_RPATH`. Binaries of dependants
+//! that do not have no rpath in any mode, see the crate docs.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::error::BuildError;
+
+enum Mode {
+    None,
+    Absolute,
+    Origin,
+}
+
+/// Sets up the rpath for the shared libui in `lib_dir`, returning it.
+pub fn configure(lib_dir: &Path, out_path: &Path) -> Result<Option<String>, BuildError> {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let darwin = target_os == "macos";
+    if !darwin && !crate::is_unix_backend(&target_os) {
+        return Ok(None);
+    }
+
+    let rpath = match mode()? {
+        Mode::None => return Ok(None),
+        Mode::Absolute => lib_dir.display().to_string(),
+        Mode::Origin => {
+            copy_to_target_dir(lib_dir, out_path, library_file(darwin))?;
+            if darwin { "@loader_path" } else { "$ORIGIN" }.to_owned()
+        }
+    };
+    println!("cargo:rustc-link-arg=-Wl,-rpath,{}", rpath);
+    Ok(Some(rpath))
+}
+
+fn mode() -> Result<Mode, BuildError> {
+    match crate::env_var("LIBUI_SYS_RPATH").as_deref() {
+        None | Some("absolute") => Ok(Mode::Absolute),
+        Some("origin") => Ok(Mode::Origin),
+        Some("none") | Some("0") | Some("false") => Ok(Mode::None),
+        Some(other) => Err(BuildError::Other(format!(
+            "Invalid LIBUI_SYS_RPATH {:?}, expected one of absolute, origin and none.",
+            other
+        ))),
+    }
+}
+
+/// Name of the shared library binaries are linked against.
+fn library_file(darwin: bool) -> &'static str {
+    if darwin {
+        "libui.A.dylib"
+    } else {
+        "libui.so.0"
+    }
+}
+
+/// Copies `file` into the directories Cargo puts binaries and tests into,
+/// which are found relative to `OUT_DIR` (`target/<profile>/build/<pkg>/out`).
+fn copy_to_target_dir(lib_dir: &Path, out_path: &Path, file: &str) -> Result<(), BuildError> {
+    let profile_dir = match out_path.ancestors().nth(3) {
+        Some(dir) => dir,
+        None => {
+            crate::warn(&format!(
+                "Unable to find the target directory from {}, {} is not copied next to the binaries",
+                out_path.display(),
+                file
+            ));
+            return Ok(());
+        }
+    };
+    for dir in &[profile_dir.to_owned(), profile_dir.join("deps")] {
+        fs::create_dir_all(dir)?;
+        fs::copy(lib_dir.join(file), dir.join(file))
+            .map_err(|err| format!("Unable to copy {} to {}: {}", file, dir.display(), err))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! The functions a shared or static library exports, read from its symbol
+//! table with the object crate.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use object::read::archive::ArchiveFile;
+use object::read::coff::ImportFile;
+use object::{Architecture, BinaryFormat, FileKind, Object, ObjectSymbol};
+
+/// The global symbols defined by the shared or static library at `path`.
+pub fn exported_symbols(path: &Path) -> Result<BTreeSet<String>, String> {
+    let data = fs::read(path).map_err(|err| err.to_string())?;
+    let mut symbols = BTreeSet::new();
+    if FileKind::parse(&*data).map_err(|err| err.to_string())? == FileKind::Archive {
+        let archive = ArchiveFile::parse(&*data).map_err(|err| err.to_string())?;
+        for member in archive.members() {
+            let member = member.map_err(|err| err.to_string())?;
+            let member = member.data(&*data).map_err(|err| err.to_string())?;
+            add_symbols(member, false, &mut symbols)?;
+        }
+    } else {
+        add_symbols(&data, true, &mut symbols)?;
+    }
+    Ok(symbols)
+}
+
+/// Adds the global symbols defined by the object file, shared library or
+/// import library member `data` to `symbols`. `shared` takes the symbols
+/// exported for dynamic linking instead of the symbol table.
+fn add_symbols(data: &[u8], shared: bool, symbols: &mut BTreeSet<String>) -> Result<(), String> {
+    match FileKind::parse(data) {
+        // Members of a Windows import library, one per function.
+        Ok(FileKind::CoffImport) => {
+            let import = ImportFile::parse(data).map_err(|err| err.to_string())?;
+            let underscored = import.architecture() == Architecture::I386;
+            add_symbol(import.symbol(), underscored, symbols);
+            return Ok(());
+        }
+        Ok(_) => {}
+        // Archives can contain files other than objects.
+        Err(_) if !shared => return Ok(()),
+        Err(err) => return Err(err.to_string()),
+    }
+
+    let file = object::File::parse(data).map_err(|err| err.to_string())?;
+    // C symbols have a leading underscore on macOS and 32-bit Windows.
+    let underscored = file.format() == BinaryFormat::MachO
+        || (file.architecture() == Architecture::I386 && file.format() != BinaryFormat::Elf);
+    if shared {
+        for export in file.exports().map_err(|err| err.to_string())? {
+            add_symbol(export.name(), underscored, symbols);
+        }
+    } else {
+        for symbol in file.symbols() {
+            if symbol.is_definition() && symbol.is_global() {
+                add_symbol(
+                    symbol.name_bytes().unwrap_or_default(),
+                    underscored,
+                    symbols,
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn add_symbol(name: &[u8], underscored: bool, symbols: &mut BTreeSet<String>) {
+    let name = String::from_utf8_lossy(name);
+    let name = if underscored {
+        name.strip_prefix('_').unwrap_or(&name)
+    } else {
+        &name
+    };
+    symbols.insert(name.to_owned());
+}
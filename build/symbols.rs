@@ -0,0 +1,101 @@
+//! Which functions of the bindings the built libui actually implements.
+//!
+//! `ui.h` declares a few functions that some backends never implemented,
+//! which otherwise only shows up as a link error in an application using
+//! them. The symbol table of the library is read and compared with the
+//! functions in the bindings; the ones found are written to
+//! `out_dir/symbols.rs` for `IMPLEMENTED_FUNCTIONS`, the others are
+//! reported as warnings. All the functions of the bindings are written to
+//! `out_dir/declared.rs`, which the tests compare the library with.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::BuildError;
+use crate::symbol_table::exported_symbols;
+
+/// Writes the functions declared in `bindings` that the library in
+/// `lib_dir` exports.
+///
+/// Without a library to read, e.g. a system-wide libui in a default search
+/// path, `None` is written instead of the list.
+pub fn write(
+    lib_dir: Option<&Path>,
+    static_linking: bool,
+    bindings: &[PathBuf],
+    out_dir: &Path,
+) -> Result<(), BuildError> {
+    let mut functions = BTreeSet::new();
+    for file in bindings {
+        functions.extend(declared_functions(&fs::read_to_string(file)?));
+    }
+
+    let library = lib_dir.and_then(|dir| {
+        crate::prebuilt::library_files(static_linking)
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.exists())
+    });
+    let exported = match library.as_ref().map(|path| exported_symbols(path)) {
+        Some(Ok(exported)) => Some(exported),
+        Some(Err(err)) => {
+            crate::warn(&format!(
+                "Unable to read the symbols of {}: {}",
+                library.unwrap().display(),
+                err
+            ));
+            None
+        }
+        None => None,
+    };
+
+    let implemented = match &exported {
+        Some(exported) => {
+            let (implemented, missing): (Vec<_>, Vec<_>) = functions
+                .iter()
+                .partition(|function| exported.contains(*function));
+            if !missing.is_empty() {
+                let missing: Vec<_> = missing.iter().map(|function| function.as_str()).collect();
+                crate::warn(&format!(
+                    "Functions declared in ui.h are missing from libui: {}",
+                    missing.join(", ")
+                ));
+            }
+            format!("Some(&{})", list(implemented))
+        }
+        None => "None".to_owned(),
+    };
+    fs::write(out_dir.join("symbols.rs"), implemented)?;
+    fs::write(out_dir.join("declared.rs"), list(&functions))?;
+    Ok(())
+}
+
+/// `names` as an array expression of string literals.
+fn list<'a, I: IntoIterator<Item = &'a String>>(names: I) -> String {
+    let mut code = String::from("[\n");
+    for name in names {
+        writeln!(code, "    \"{}\",", name).unwrap();
+    }
+    code.push(']');
+    code
+}
+
+/// The names of the libui functions in the bindings `code`, be they
+/// declared in an extern block or as methods of the `dlopen` library
+/// struct.
+fn declared_functions(code: &str) -> Vec<String> {
+    code.split("fn ")
+        .skip(1)
+        .filter_map(|rest| {
+            let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))?;
+            let (name, rest) = rest.split_at(end);
+            if name.starts_with("ui") && rest.starts_with('(') {
+                Some(name.to_owned())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,116 @@
+//! Support for linking against a system-wide libui found with pkg-config.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::error::BuildError;
+
+/// The oldest system libui that provides everything the bundled `ui.h`
+/// declares.
+const MIN_VERSION: &str = "0.4.1";
+
+/// pkg-config package names libui is known to be installed under.
+const PKG_NAMES: &[&str] = &["libui", "ui"];
+
+/// Include directories searched for `ui.h` in addition to the ones reported
+/// by pkg-config, which omits the compiler defaults.
+const DEFAULT_INCLUDE_DIRS: &[&str] = &["/usr/include", "/usr/local/include"];
+
+pub struct SystemLibrary {
+    /// Path to the system `ui.h`.
+    pub header: PathBuf,
+    /// Directory containing the library, unless it is in a default location.
+    pub lib_dir: Option<PathBuf>,
+}
+
+enum Request {
+    No,
+    Preferred,
+    Required,
+}
+
+/// `LIBUI_SYS_USE_SYSTEM` takes precedence over the `system` feature, and
+/// unlike the feature makes the build fail if no suitable libui is found.
+fn request() -> Request {
+    match crate::env_var("LIBUI_SYS_USE_SYSTEM") {
+        Some(ref v) if v == "0" || v.eq_ignore_ascii_case("false") => Request::No,
+        Some(_) => Request::Required,
+        None if env::var_os("CARGO_FEATURE_SYSTEM").is_some() => Request::Preferred,
+        None => Request::No,
+    }
+}
+
+/// Looks up a system libui if one was requested, emitting the link
+/// directives for it. Returns `None` when the bundled libui should be built.
+pub fn find(static_linking: bool) -> Result<Option<SystemLibrary>, BuildError> {
+    let required = match request() {
+        Request::No => return Ok(None),
+        Request::Preferred => false,
+        Request::Required => true,
+    };
+
+    match probe(static_linking) {
+        Ok(lib) => Ok(Some(lib)),
+        Err(err) if !required && crate::source_dir()?.join("ui.h").exists() => {
+            crate::warn(&format!(
+                "Unable to use the system libui ({}), building the bundled one instead",
+                err
+            ));
+            Ok(None)
+        }
+        Err(err) => Err(BuildError::Other(format!(
+            "Unable to use the system libui: {}\n\n\
+             Install libui {} or newer along with its pkg-config file, or unset \
+             LIBUI_SYS_USE_SYSTEM and disable the `system` feature to build the bundled libui.",
+            err, MIN_VERSION
+        ))),
+    }
+}
+
+fn probe(static_linking: bool) -> Result<SystemLibrary, String> {
+    let mut config = pkg_config::Config::new();
+    config.atleast_version(MIN_VERSION).statik(static_linking);
+
+    let mut errors = Vec::new();
+    for name in PKG_NAMES {
+        // Link directives are only emitted once the package turned out to be
+        // usable, so that falling back to the bundled libui stays clean, and
+        // not at all when libui is loaded at runtime.
+        let lib = match config.cargo_metadata(false).probe(name) {
+            Ok(lib) => lib,
+            Err(err) => {
+                errors.push(err.to_string());
+                continue;
+            }
+        };
+
+        let header = lib
+            .include_paths
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(DEFAULT_INCLUDE_DIRS.iter().map(Path::new))
+            .map(|dir| dir.join("ui.h"))
+            .find(|header| header.exists());
+        return match header {
+            Some(header) if crate::is_dlopen() => Ok(SystemLibrary {
+                header,
+                lib_dir: lib.link_paths.first().cloned(),
+            }),
+            Some(header) => {
+                config
+                    .cargo_metadata(true)
+                    .probe(name)
+                    .map_err(|err| err.to_string())?;
+                Ok(SystemLibrary {
+                    header,
+                    lib_dir: lib.link_paths.first().cloned(),
+                })
+            }
+            None => Err(format!(
+                "pkg-config package {} was found, but its ui.h was not",
+                name
+            )),
+        };
+    }
+    Err(errors.join("\n"))
+}
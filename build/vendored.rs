@@ -0,0 +1,132 @@
+//! Builds libui directly with the `cc` crate, without meson and ninja.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::BuildError;
+use crate::link_deps;
+use crate::{meson, BundledLibrary};
+
+/// Compiles the libui sources at `src` into a `libui.a` or `libui.so.0` in
+/// `out`, against the `gtk` found by pkg-config.
+pub fn build(
+    src: &Path,
+    out: &Path,
+    static_linking: bool,
+    gtk: &pkg_config::Library,
+) -> Result<BundledLibrary, BuildError> {
+    fs::create_dir_all(out)?;
+
+    // cc reads the compiler, archiver and flags from these itself, but only
+    // tells Cargo to watch them along with its link directives, which are
+    // turned off below. It combines the flags of all the variants.
+    for name in &["CC", "CFLAGS", "AR"] {
+        for var in &crate::target_env_var_names(name) {
+            crate::env_var_os(var);
+        }
+    }
+
+    let mut build = cc::Build::new();
+    build
+        .cargo_metadata(false)
+        .out_dir(out)
+        .include(src)
+        .includes(&gtk.include_paths)
+        .files(c_sources(&src.join("common"))?)
+        .files(c_sources(&src.join("unix"))?)
+        // Match the flags libui's meson.build applies to the unix backend.
+        // ui.h marks the API for export only with libui_EXPORTS, everything
+        // else stays hidden.
+        .define("libui_EXPORTS", None)
+        .flag_if_supported("-fvisibility=hidden")
+        .flag_if_supported("-std=c99")
+        .flag_if_supported("-Wno-unused-parameter")
+        .flag_if_supported("-Wno-switch");
+    apply_buildtype(&mut build)?;
+
+    if static_linking {
+        build
+            .define("_UI_STATIC", None)
+            .try_compile("ui")
+            .map_err(compile_error)?;
+        let args = link_deps::gtk_link_args(&gtk.link_paths, &gtk.libs);
+        return Ok(BundledLibrary {
+            lib_dir: out.to_path_buf(),
+            dependencies: link_deps::from_link_args(&args),
+        });
+    }
+
+    // cc only produces static archives, so link the shared library by hand
+    // with the same compiler driver, mirroring meson's soname.
+    let objects = build.try_compile_intermediates().map_err(compile_error)?;
+    let mut cmd = build
+        .try_get_compiler()
+        .map_err(compile_error)?
+        .to_command();
+    cmd.arg("-shared")
+        .arg("-Wl,-soname,libui.so.0")
+        .args(&objects)
+        .arg("-o")
+        .arg(out.join("libui.so.0"))
+        .args(link_deps::gtk_link_args(&gtk.link_paths, &gtk.libs));
+    link(cmd)?;
+
+    Ok(BundledLibrary {
+        lib_dir: out.to_path_buf(),
+        dependencies: Vec::new(),
+    })
+}
+
+/// Applies a meson build type given with `LIBUI_SYS_BUILDTYPE` to `build`,
+/// which otherwise follows the Cargo profile.
+fn apply_buildtype(build: &mut cc::Build) -> Result<(), BuildError> {
+    let buildtype = match meson::buildtype_override()? {
+        Some(buildtype) => buildtype,
+        None => return Ok(()),
+    };
+    let (opt_level, debug) = match buildtype.as_str() {
+        "debug" => ("0", true),
+        "debugoptimized" => ("2", true),
+        "release" => ("3", false),
+        "minsize" => ("s", false),
+        // `plain` and `custom` have no cc equivalent, keep the profile.
+        _ => return Ok(()),
+    };
+    build.opt_level_str(opt_level).debug(debug);
+    Ok(())
+}
+
+fn link(mut cmd: Command) -> Result<(), BuildError> {
+    let out = cmd.output().map_err(|err| BuildError::spawn(&cmd, err))?;
+    if !out.status.success() {
+        return Err(BuildError::CommandFailed {
+            command: "Linking libui.so.0".to_owned(),
+            output: String::from_utf8_lossy(&out.stderr).into_owned(),
+        });
+    }
+    Ok(())
+}
+
+fn compile_error(err: cc::Error) -> BuildError {
+    BuildError::CommandFailed {
+        command: "Compiling libui".to_owned(),
+        output: err.to_string(),
+    }
+}
+
+fn c_sources(dir: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let mut sources = Vec::new();
+    let entries =
+        fs::read_dir(dir).map_err(|err| format!("Unable to read {}: {}", dir.display(), err))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension() == Some(OsStr::new("c")) {
+            sources.push(path);
+        }
+    }
+    // Keep the archive contents stable between runs.
+    sources.sort();
+    Ok(sources)
+}
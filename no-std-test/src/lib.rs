@@ -0,0 +1,38 @@
+//! Builds against libui-sys from a `no_std` crate, which fails if the
+//! bindings need anything from `std`.
+//!
+//! Being `no_std` alone proves nothing, as a dependency can still pull `std`
+//! in. The crate therefore has a panic handler of its own, which collides
+//! with the one of `std` as soon as `std` is linked.
+
+#![no_std]
+
+use core::ptr;
+
+use libui_sys::*;
+
+/// Initialises libui, using a few items of the bindings so that they are
+/// type checked here.
+///
+/// # Safety
+///
+/// Has to be called from the main thread.
+pub unsafe fn init() -> bool {
+    let mut options = uiInitOptions { Size: 0 };
+    let err = uiInit(&mut options);
+    if !err.is_null() {
+        uiFreeInitError(err);
+        return false;
+    }
+    uiQueueMain(None, ptr::null_mut());
+    true
+}
+
+// Not for the test harness, which brings `std` along.
+#[cfg(not(test))]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -0,0 +1,75 @@
+//! Loading libui at runtime, enabled by the `dlopen` feature.
+
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+
+/// File name libui is installed under on the target, to be passed to
+/// `LibUi::load` when it is on the library search path.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub const LIBRARY_NAME: &str = "libui.so.0";
+#[cfg(target_os = "macos")]
+pub const LIBRARY_NAME: &str = "libui.A.dylib";
+#[cfg(windows)]
+pub const LIBRARY_NAME: &str = "libui.dll";
+
+/// Why libui could not be loaded.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The library itself could not be opened.
+    Open(libloading::Error),
+    /// The library lacks some functions, e.g. because it is older than the
+    /// bindings.
+    MissingSymbols(Vec<&'static str>),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Open(err) => write!(f, "unable to load libui: {}", err),
+            LoadError::MissingSymbols(symbols) => {
+                write!(f, "libui lacks the functions {}", symbols.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Open(err) => Some(err),
+            LoadError::MissingSymbols(_) => None,
+        }
+    }
+}
+
+macro_rules! impl_load {
+    ($lib:ty) => {
+        impl $lib {
+            /// Loads libui from `path`, failing unless every function is
+            /// there. Use `new` and `missing_symbols` to cope with an
+            /// incomplete library instead.
+            ///
+            /// # Safety
+            ///
+            /// Loading a library runs its initialisation routines, see
+            /// `libloading::Library::new`.
+            pub unsafe fn load<P: AsRef<OsStr>>(path: P) -> Result<Self, LoadError> {
+                let lib = Self::new(path).map_err(LoadError::Open)?;
+                let missing = lib.missing_symbols();
+                if !missing.is_empty() {
+                    return Err(LoadError::MissingSymbols(missing));
+                }
+                Ok(lib)
+            }
+        }
+    };
+}
+
+impl_load!(crate::LibUi);
+#[cfg(libui_backend = "unix")]
+impl_load!(crate::unix::LibUiUnix);
+#[cfg(libui_backend = "windows")]
+impl_load!(crate::windows::LibUiWindows);
+#[cfg(libui_backend = "darwin")]
+impl_load!(crate::darwin::LibUiDarwin);
@@ -1,5 +1,94 @@
+//! Low-level bindings for libui.
+//!
+//! With the `ng` feature the bindings are for libui-ng instead, which keeps
+//! the API of andlabs/libui and adds to it. [`LIBUI_NG`] tells which one was
+//! built. Dependants that want to compile the libui-ng only parts of their
+//! code conditionally can get a `libui_ng` cfg of their own from the
+//! `DEP_UI_NG` metadata, which is `1` for libui-ng, in their build script:
+//!
+//! ```no_run
+//! // build.rs
+//! println!("cargo:rustc-check-cfg=cfg(libui_ng)");
+//! if std::env::var("DEP_UI_NG").as_deref() == Ok("1") {
+//!     println!("cargo:rustc-cfg=libui_ng");
+//! }
+//! ```
+//!
+//! This needs a direct dependency on `libui-sys`, which links libui and so
+//! is the one passing the metadata on.
+//!
+//! # Finding a shared libui at runtime
+//!
+//! A shared bundled libui is found at runtime through an rpath, chosen with
+//! `LIBUI_SYS_RPATH`: the directory it was built in (`absolute`, the
+//! default), the directory of the binary (`origin`, for which `libui.so.0`
+//! or `libui.A.dylib` is copied next to the binaries in `target/<profile>/`)
+//! or none (`none`). Cargo only passes the rpath on to the tests and
+//! examples of `libui-sys` itself though, so the binaries of a dependant get
+//! none unless it adds the rpath exported as `DEP_UI_RPATH` in its build
+//! script:
+//!
+//! ```no_run
+//! // build.rs
+//! if let Ok(rpath) = std::env::var("DEP_UI_RPATH") {
+//!     println!("cargo:rustc-link-arg=-Wl,-rpath,{}", rpath);
+//! }
+//! ```
+//!
+//! Like `DEP_UI_NG`, `DEP_UI_RPATH` only reaches direct dependants. Further
+//! up, the rpath has to be given to the linker directly, e.g. with
+//! `-C link-arg=-Wl,-rpath,$ORIGIN` in `RUSTFLAGS`. Without any of this,
+//! binaries only find libui through `LD_LIBRARY_PATH` or a system-wide
+//! install.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+#![cfg_attr(feature = "dlopen", allow(clippy::missing_safety_doc))]
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+/// Whether the bindings are for libui-ng rather than andlabs/libui.
+pub const LIBUI_NG: bool = cfg!(libui_ng);
+
+/// The functions of the bindings that the libui built or linked by this
+/// crate implements, by name. Some functions of `ui.h` are missing from
+/// some backends, and calling them ends in a link error.
+///
+/// `None` if the library could not be inspected, e.g. for a system-wide
+/// libui without a known directory or with the `dlopen` feature.
+pub const IMPLEMENTED_FUNCTIONS: Option<&[&str]> =
+    include!(concat!(env!("OUT_DIR"), "/symbols.rs"));
+
+#[cfg(feature = "dlopen")]
+mod dlopen;
+#[cfg(feature = "dlopen")]
+pub use dlopen::{LoadError, LIBRARY_NAME};
+
+/// Bindings for `ui_unix.h`, the API for writing controls backed by GTK
+/// widgets. GTK types are opaque.
+#[cfg(libui_backend = "unix")]
+pub mod unix {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/ui_unix.rs"));
+}
+
+/// Bindings for `ui_windows.h`, the API for writing controls backed by
+/// Win32 windows.
+#[cfg(libui_backend = "windows")]
+pub mod windows {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/ui_windows.rs"));
+}
+
+/// Bindings for `ui_darwin.h`, the API for writing controls backed by Cocoa
+/// views. Cocoa types are opaque.
+#[cfg(libui_backend = "darwin")]
+pub mod darwin {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/ui_darwin.rs"));
+}
@@ -0,0 +1,411 @@
+//! Generates the ABI checks for the libui-sys bindings.
+//!
+//! The bindings are read back from libui-sys (DEP_UI_BINDINGS) and a C file
+//! is generated that reports, for the same items, what the C compiler makes
+//! of ui.h: struct sizes, alignments and field offsets, enum values and
+//! function addresses. Function signatures are checked by the C compiler
+//! itself, by assigning every function to a pointer of the type the
+//! bindings declare. `tests/abi.rs` compares the rest with the Rust side.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use quote::ToTokens;
+use syn::{Fields, ForeignItem, ImplItem, Item, ReturnType, Type, TypeBareFn};
+
+/// What the checks cover.
+#[derive(Default)]
+struct Items {
+    /// Structs with their public fields and the Rust types of those.
+    structs: Vec<(String, Vec<(String, Type)>)>,
+    /// Enum types with their variants.
+    enums: Vec<(String, Vec<String>)>,
+    /// Functions with their parameter and return types.
+    functions: Vec<(String, Vec<Type>, ReturnType)>,
+    /// Callback aliases, which the C side spells out.
+    aliases: HashMap<String, TypeBareFn>,
+}
+
+fn main() {
+    let bindings = PathBuf::from(
+        env::var_os("DEP_UI_BINDINGS")
+            .expect("DEP_UI_BINDINGS is not set, libui-sys is too old or did not build"),
+    );
+    let include = env::var_os("DEP_UI_INCLUDE").expect("DEP_UI_INCLUDE is not set");
+    let static_linking = env::var("DEP_UI_STATIC").is_ok_and(|value| value == "1");
+    let target = env::var("TARGET").unwrap();
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    println!("cargo:rerun-if-changed={}", bindings.display());
+
+    let code = fs::read_to_string(&bindings).unwrap();
+    let file = syn::parse_file(&code).unwrap();
+    let items = collect(&file.items);
+
+    // With a shared libui on Windows the C code only sees the address of
+    // the import thunk, while Rust uses dllimport and gets the function.
+    let compare_addresses = static_linking || !target.contains("windows");
+
+    fs::write(out_dir.join("abi.c"), c_checks(&items)).unwrap();
+    fs::write(
+        out_dir.join("abi.rs"),
+        rust_checks(&items, compare_addresses),
+    )
+    .unwrap();
+
+    let mut build = cc::Build::new();
+    build.file(out_dir.join("abi.c")).include(&include);
+    if static_linking {
+        build.define("_UI_STATIC", None);
+    }
+    // Turn mismatched function signatures into errors.
+    build
+        .flag_if_supported("-Werror=incompatible-pointer-types")
+        .flag_if_supported("/we4113")
+        .flag_if_supported("/we4133")
+        .flag_if_supported("/we4047");
+    build.compile("systest_abi");
+}
+
+fn collect(file_items: &[Item]) -> Items {
+    let mut items = Items::default();
+    for item in file_items {
+        match item {
+            Item::Struct(item) => {
+                // The enum newtypes have a tuple field and are checked through
+                // their values instead.
+                let fields = match &item.fields {
+                    Fields::Named(fields) => fields,
+                    _ => continue,
+                };
+                // Opaque structs only have a private placeholder field.
+                let fields: Vec<_> = fields
+                    .named
+                    .iter()
+                    .filter(|field| matches!(field.vis, syn::Visibility::Public(_)))
+                    .map(|field| (field.ident.as_ref().unwrap().to_string(), field.ty.clone()))
+                    .collect();
+                if !fields.is_empty() {
+                    items.structs.push((item.ident.to_string(), fields));
+                }
+            }
+            Item::Impl(item) if item.trait_.is_none() => {
+                let ty = item.self_ty.to_token_stream().to_string();
+                let variants: Vec<_> = item
+                    .items
+                    .iter()
+                    .filter_map(|item| match item {
+                        ImplItem::Const(item) => Some(item.ident.to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                if !variants.is_empty() {
+                    items.enums.push((ty, variants));
+                }
+            }
+            Item::ForeignMod(item) => {
+                for item in &item.items {
+                    if let ForeignItem::Fn(item) = item {
+                        let params = item
+                            .sig
+                            .inputs
+                            .iter()
+                            .filter_map(|arg| match arg {
+                                syn::FnArg::Typed(arg) => Some((*arg.ty).clone()),
+                                syn::FnArg::Receiver(_) => None,
+                            })
+                            .collect();
+                        let output = item.sig.output.clone();
+                        items
+                            .functions
+                            .push((item.sig.ident.to_string(), params, output));
+                    }
+                }
+            }
+            Item::Type(item) => {
+                if let Type::BareFn(function) = &*item.ty {
+                    items
+                        .aliases
+                        .insert(item.ident.to_string(), function.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+fn c_checks(items: &Items) -> String {
+    let mut c = String::from(
+        "#include <stddef.h>\n\
+         #include <stdint.h>\n\
+         #include <time.h>\n\
+         #include \"ui.h\"\n\
+         \n\
+         #ifdef _MSC_VER\n\
+         #define SYSTEST_ALIGNOF(t) __alignof(t)\n\
+         #else\n\
+         #define SYSTEST_ALIGNOF(t) _Alignof(t)\n\
+         #endif\n\n",
+    );
+    for (name, fields) in &items.structs {
+        let ty = c_name(name);
+        writeln!(
+            c,
+            "size_t systest_size_{}(void) {{ return sizeof({}); }}",
+            name, ty
+        )
+        .unwrap();
+        writeln!(
+            c,
+            "size_t systest_align_{}(void) {{ return SYSTEST_ALIGNOF({}); }}",
+            name, ty
+        )
+        .unwrap();
+        for (field, _) in fields {
+            writeln!(
+                c,
+                "size_t systest_offset_{0}_{1}(void) {{ return offsetof({2}, {1}); }}",
+                name, field, ty
+            )
+            .unwrap();
+            writeln!(
+                c,
+                "size_t systest_field_size_{0}_{1}(void) {{ return sizeof((({2} *)0)->{1}); }}",
+                name, field, ty
+            )
+            .unwrap();
+        }
+    }
+    for (_, variants) in &items.enums {
+        for variant in variants {
+            writeln!(
+                c,
+                "unsigned long long systest_enum_{0}(void) {{ return (unsigned long long){0}; }}",
+                variant
+            )
+            .unwrap();
+        }
+    }
+    for (name, params, output) in &items.functions {
+        writeln!(
+            c,
+            "void *systest_fn_{0}(void) {{ return (void *)&{0}; }}",
+            name
+        )
+        .unwrap();
+        match function_pointer(items, params, output, &format!("systest_sig_{}", name)) {
+            Some(declaration) => {
+                writeln!(c, "{} = &{};", declaration, name).unwrap();
+            }
+            None => println!(
+                "cargo:warning=The signature of {} has types the ABI checks do not know",
+                name
+            ),
+        }
+    }
+    c
+}
+
+fn rust_checks(items: &Items, compare_addresses: bool) -> String {
+    let mut rust = String::from("extern \"C\" {\n");
+    for (name, fields) in &items.structs {
+        writeln!(rust, "    fn systest_size_{}() -> usize;", name).unwrap();
+        writeln!(rust, "    fn systest_align_{}() -> usize;", name).unwrap();
+        for (field, _) in fields {
+            writeln!(rust, "    fn systest_offset_{}_{}() -> usize;", name, field).unwrap();
+            writeln!(
+                rust,
+                "    fn systest_field_size_{}_{}() -> usize;",
+                name, field
+            )
+            .unwrap();
+        }
+    }
+    for (_, variants) in &items.enums {
+        for variant in variants {
+            writeln!(rust, "    fn systest_enum_{}() -> u64;", variant).unwrap();
+        }
+    }
+    for (name, _, _) in &items.functions {
+        writeln!(rust, "    fn systest_fn_{}() -> *const c_void;", name).unwrap();
+    }
+    rust.push_str("}\n\n");
+
+    rust.push_str("fn check_structs(checks: &mut Checks) {\n    unsafe {\n");
+    for (name, fields) in &items.structs {
+        writeln!(
+            rust,
+            "        checks.equal(\"size of {0}\", mem::size_of::<{0}>(), systest_size_{0}());",
+            name
+        )
+        .unwrap();
+        writeln!(
+            rust,
+            "        checks.equal(\"alignment of {0}\", mem::align_of::<{0}>(), systest_align_{0}());",
+            name
+        )
+        .unwrap();
+        for (field, ty) in fields {
+            writeln!(
+                rust,
+                "        checks.equal(\"offset of {0}.{1}\", mem::offset_of!({0}, {1}), systest_offset_{0}_{1}());",
+                name, field
+            )
+            .unwrap();
+            writeln!(
+                rust,
+                "        checks.equal(\"size of {0}.{1}\", mem::size_of::<{2}>(), systest_field_size_{0}_{1}());",
+                name,
+                field,
+                ty.to_token_stream()
+            )
+            .unwrap();
+        }
+    }
+    rust.push_str("    }\n}\n\n");
+
+    rust.push_str("fn check_enums(checks: &mut Checks) {\n    unsafe {\n");
+    for (ty, variants) in &items.enums {
+        for variant in variants {
+            writeln!(
+                rust,
+                "        checks.equal(\"value of {1}\", {0}::{1}.0 as u64, systest_enum_{1}());",
+                ty, variant
+            )
+            .unwrap();
+        }
+    }
+    rust.push_str("    }\n}\n\n");
+
+    rust.push_str("fn check_functions(checks: &mut Checks) {\n    unsafe {\n");
+    if compare_addresses {
+        for (name, _, _) in &items.functions {
+            writeln!(
+                rust,
+                "        checks.equal(\"address of {0}\", {0} as *const c_void, systest_fn_{0}());",
+                name
+            )
+            .unwrap();
+        }
+    } else {
+        // Still makes sure every function links.
+        for (name, _, _) in &items.functions {
+            writeln!(
+                rust,
+                "        checks.equal(\"{0} is linked\", !systest_fn_{0}().is_null(), true);",
+                name
+            )
+            .unwrap();
+        }
+    }
+    rust.push_str("    }\n}\n");
+    rust
+}
+
+/// A C declaration of `name` as a pointer to a function with `params` and
+/// `output`.
+fn function_pointer(
+    items: &Items,
+    params: &[Type],
+    output: &ReturnType,
+    name: &str,
+) -> Option<String> {
+    let params = params
+        .iter()
+        .map(|param| declare(items, param, String::new(), false))
+        .collect::<Option<Vec<_>>>()?;
+    let params = if params.is_empty() {
+        "void".to_owned()
+    } else {
+        params.join(", ")
+    };
+    let declarator = format!("(*{})({})", name, params);
+    match output {
+        ReturnType::Default => Some(format!("void {}", declarator)),
+        ReturnType::Type(_, ty) => declare(items, ty, declarator, false),
+    }
+}
+
+/// Spells out the Rust type `ty` as a C declaration of `declarator`, which
+/// is empty for abstract declarations like parameter types. `is_const`
+/// qualifies the declared object itself.
+fn declare(items: &Items, ty: &Type, declarator: String, is_const: bool) -> Option<String> {
+    match ty {
+        Type::Ptr(ptr) => {
+            let declarator = if is_const {
+                format!("*const {}", declarator)
+            } else {
+                format!("*{}", declarator)
+            };
+            declare(items, &ptr.elem, declarator, ptr.const_token.is_some())
+        }
+        Type::BareFn(function) => {
+            let params: Vec<Type> = function.inputs.iter().map(|arg| arg.ty.clone()).collect();
+            function_pointer(items, &params, &function.output, &declarator)
+        }
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let name = segment.ident.to_string();
+            // Callbacks are `Option<alias>`, with the alias naming a
+            // function pointer type.
+            if name == "Option" {
+                let inner = match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+                        syn::GenericArgument::Type(inner) => inner,
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                return declare(items, inner, declarator, is_const);
+            }
+            if let Some(function) = items.aliases.get(&name) {
+                return declare(items, &Type::BareFn(function.clone()), declarator, is_const);
+            }
+            let base = c_name(&name);
+            let qualifier = if is_const { " const" } else { "" };
+            Some(
+                format!("{}{} {}", base, qualifier, declarator)
+                    .trim_end()
+                    .to_owned(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// The C name of a Rust type in the bindings.
+fn c_name(name: &str) -> &str {
+    match name {
+        "c_char" => "char",
+        "c_schar" => "signed char",
+        "c_uchar" => "unsigned char",
+        "c_short" => "short",
+        "c_ushort" => "unsigned short",
+        "c_int" => "int",
+        "c_uint" => "unsigned int",
+        "c_long" => "long",
+        "c_ulong" => "unsigned long",
+        "c_longlong" => "long long",
+        "c_ulonglong" => "unsigned long long",
+        "c_float" | "f32" => "float",
+        "c_double" | "f64" => "double",
+        "c_void" => "void",
+        "usize" => "size_t",
+        "isize" => "ptrdiff_t",
+        "u8" => "uint8_t",
+        "u16" => "uint16_t",
+        "u32" => "uint32_t",
+        "u64" => "uint64_t",
+        "i8" => "int8_t",
+        "i16" => "int16_t",
+        "i32" => "int32_t",
+        "i64" => "int64_t",
+        // A struct tag rather than a typedef.
+        "tm" => "struct tm",
+        // libui's own types keep their names.
+        other => other,
+    }
+}
@@ -0,0 +1 @@
+//! ABI checks for libui-sys, see `tests/abi.rs`.
@@ -0,0 +1,56 @@
+//! Compares the layouts, values and functions of the bindings with what the
+//! C compiler makes of ui.h, see `build.rs`.
+
+use std::ffi::c_void;
+use std::fmt::Debug;
+use std::mem;
+
+use libui_sys::*;
+// The C side of the checks is compiled into the library.
+extern crate systest;
+
+include!(concat!(env!("OUT_DIR"), "/abi.rs"));
+
+/// Collects mismatches, so that one run reports all of them.
+#[derive(Default)]
+struct Checks {
+    errors: Vec<String>,
+}
+
+impl Checks {
+    fn equal<T: PartialEq + Debug>(&mut self, what: &str, rust: T, c: T) {
+        if rust != c {
+            self.errors
+                .push(format!("{}: {:?} in Rust, {:?} in C", what, rust, c));
+        }
+    }
+
+    fn assert_ok(self) {
+        assert!(
+            self.errors.is_empty(),
+            "ABI mismatches:\n{}",
+            self.errors.join("\n")
+        );
+    }
+}
+
+#[test]
+fn structs() {
+    let mut checks = Checks::default();
+    check_structs(&mut checks);
+    checks.assert_ok();
+}
+
+#[test]
+fn enums() {
+    let mut checks = Checks::default();
+    check_enums(&mut checks);
+    checks.assert_ok();
+}
+
+#[test]
+fn functions() {
+    let mut checks = Checks::default();
+    check_functions(&mut checks);
+    checks.assert_ok();
+}
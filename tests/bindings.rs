@@ -0,0 +1,51 @@
+#![cfg(feature = "bindgen")]
+
+use std::fs;
+use std::path::Path;
+
+/// Generated files in OUT_DIR and the names of their pre-generated
+/// counterparts.
+const FILES: &[(&str, &str)] = &[
+    ("bindings.rs", "ui"),
+    ("ui_unix.rs", "ui_unix"),
+    ("ui_windows.rs", "ui_windows"),
+    ("ui_darwin.rs", "ui_darwin"),
+];
+
+#[test]
+#[ignore = "no pre-generated bindings are checked in under bindings/ yet"]
+fn pregenerated_bindings_are_up_to_date() {
+    let mut dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("bindings");
+    if cfg!(feature = "ng") {
+        dir.push("ng");
+    }
+    let dir = dir.join(env!("LIBUI_SYS_TARGET"));
+    let suffix = if cfg!(feature = "dlopen") {
+        "_dlopen"
+    } else {
+        ""
+    };
+
+    for &(generated, pregenerated) in FILES {
+        let generated = Path::new(env!("OUT_DIR")).join(generated);
+        let pregenerated = dir.join(format!("{}{}.rs", pregenerated, suffix));
+        if !generated.exists() {
+            continue;
+        }
+        assert!(
+            pregenerated.exists(),
+            "there are no pre-generated bindings at {}, build with \
+             LIBUI_SYS_UPDATE_BINDINGS=1 and the bindgen feature to create them",
+            pregenerated.display()
+        );
+
+        let generated = fs::read_to_string(&generated).unwrap();
+        let pregenerated_contents = fs::read_to_string(&pregenerated).unwrap();
+        assert!(
+            generated == pregenerated_contents,
+            "pre-generated bindings in {} are outdated, rebuild with \
+             LIBUI_SYS_UPDATE_BINDINGS=1 and the bindgen feature to refresh them",
+            pregenerated.display()
+        );
+    }
+}
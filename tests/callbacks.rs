@@ -0,0 +1,42 @@
+//! The callback aliases have the shapes of the C callbacks and are what the
+//! bindings use. Both are checked at compile time.
+
+#![cfg(not(feature = "dlopen"))]
+
+use std::os::raw::{c_int, c_void};
+
+use libui_sys::*;
+
+unsafe extern "C" fn on_clicked(_: *mut uiButton, _: *mut c_void) {}
+
+unsafe extern "C" fn on_closing(_: *mut uiWindow, _: *mut c_void) -> c_int {
+    0
+}
+
+unsafe extern "C" fn queued(_: *mut c_void) {}
+
+unsafe extern "C" fn tick(_: *mut c_void) -> c_int {
+    0
+}
+
+unsafe extern "C" fn draw(_: *mut uiAreaHandler, _: *mut uiArea, _: *mut uiAreaDrawParams) {}
+
+#[test]
+fn aliases_match_the_callbacks() {
+    let _: uiButtonOnClickedFn = on_clicked;
+    let _: uiWindowOnClosingFn = on_closing;
+    let _: uiQueueMainFn = queued;
+    let _: uiTimerFn = tick;
+    let _: uiAreaHandlerDrawFn = draw;
+}
+
+#[test]
+fn bindings_use_the_aliases() {
+    let _: unsafe extern "C" fn(*mut uiButton, Option<uiButtonOnClickedFn>, *mut c_void) =
+        uiButtonOnClicked;
+    let _: unsafe extern "C" fn(*mut uiWindow, Option<uiWindowOnClosingFn>, *mut c_void) =
+        uiWindowOnClosing;
+    let _: unsafe extern "C" fn(Option<uiQueueMainFn>, *mut c_void) = uiQueueMain;
+    let _: unsafe extern "C" fn(c_int, Option<uiTimerFn>, *mut c_void) = uiTimer;
+    let _: fn(&uiAreaHandler) -> Option<uiAreaHandlerDrawFn> = |handler| handler.Draw;
+}
@@ -0,0 +1,126 @@
+//! The meson cross files and toolchain names generated for a few targets.
+//!
+//! These only compare the generated text with what meson and the usual GNU
+//! toolchains expect. Nothing here runs an actual cross build, which needs a
+//! cross toolchain and the target's GTK development files (or MinGW for
+//! Windows) and is not part of the CI matrix.
+
+#[path = "../build/cross.rs"]
+#[allow(dead_code)]
+mod cross;
+#[path = "../build/meson_syntax.rs"]
+mod meson_syntax;
+
+use std::ffi::OsString;
+
+// The environment lookups of the build script. The tests leave every
+// override unset, so the cross files get the defaults.
+fn env_var(_name: &str) -> Option<String> {
+    None
+}
+
+#[allow(dead_code)]
+fn env_var_os(_name: &str) -> Option<OsString> {
+    None
+}
+
+fn target_env_var(_name: &str) -> Option<String> {
+    None
+}
+
+#[test]
+fn aarch64_linux_cross_file() {
+    let expected = "\
+[binaries]
+c = ['aarch64-linux-gnu-gcc']
+cpp = ['aarch64-linux-gnu-g++']
+ar = ['aarch64-linux-gnu-ar']
+pkgconfig = ['pkg-config']
+
+[properties]
+
+[host_machine]
+system = 'linux'
+cpu_family = 'aarch64'
+cpu = 'aarch64'
+endian = 'little'
+";
+    assert_eq!(
+        cross::cross_file("aarch64-unknown-linux-gnu", "aarch64", "linux", "little"),
+        expected
+    );
+}
+
+#[test]
+fn i686_windows_cross_file() {
+    let expected = "\
+[binaries]
+c = ['i686-w64-mingw32-gcc']
+cpp = ['i686-w64-mingw32-g++']
+ar = ['i686-w64-mingw32-ar']
+windres = ['i686-w64-mingw32-windres']
+pkgconfig = ['pkg-config']
+
+[properties]
+
+[host_machine]
+system = 'windows'
+cpu_family = 'x86'
+cpu = 'i686'
+endian = 'little'
+";
+    assert_eq!(
+        cross::cross_file("i686-pc-windows-gnu", "x86", "windows", "little"),
+        expected
+    );
+}
+
+#[test]
+fn gnu_prefixes() {
+    assert_eq!(
+        cross::gnu_prefix("aarch64-unknown-linux-gnu"),
+        "aarch64-linux-gnu"
+    );
+    assert_eq!(
+        cross::gnu_prefix("i686-unknown-linux-gnu"),
+        "i686-linux-gnu"
+    );
+    assert_eq!(
+        cross::gnu_prefix("armv7-unknown-linux-gnueabihf"),
+        "arm-linux-gnueabihf"
+    );
+    assert_eq!(
+        cross::gnu_prefix("arm-unknown-linux-gnueabi"),
+        "arm-linux-gnueabi"
+    );
+    assert_eq!(
+        cross::gnu_prefix("thumbv7neon-unknown-linux-gnueabihf"),
+        "arm-linux-gnueabihf"
+    );
+    assert_eq!(
+        cross::gnu_prefix("armeb-unknown-linux-gnueabi"),
+        "armeb-linux-gnueabi"
+    );
+    assert_eq!(cross::gnu_prefix("i686-pc-windows-gnu"), "i686-w64-mingw32");
+    assert_eq!(
+        cross::gnu_prefix("x86_64-pc-windows-gnu"),
+        "x86_64-w64-mingw32"
+    );
+}
+
+#[cfg(feature = "bindgen")]
+#[test]
+fn clang_targets() {
+    assert_eq!(
+        cross::clang_target("aarch64-unknown-linux-gnu"),
+        "aarch64-unknown-linux-gnu"
+    );
+    assert_eq!(
+        cross::clang_target("i686-unknown-linux-gnu"),
+        "i686-unknown-linux-gnu"
+    );
+    assert_eq!(
+        cross::clang_target("riscv64gc-unknown-linux-gnu"),
+        "riscv64-unknown-linux-gnu"
+    );
+}
@@ -0,0 +1,126 @@
+//! The comments of ui.h end up as documentation of the bindings.
+
+#![cfg(not(feature = "dlopen"))]
+
+use syn::visit::Visit;
+use syn::{Attribute, Expr, ExprLit, ForeignItem, Item, Lit, Meta};
+
+const BINDINGS: &str = include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+/// The lines of the `#[doc]` attributes among `attrs`, whether they are
+/// written as `///` lines, `/** */` blocks or attributes.
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        let meta = match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => meta,
+            _ => continue,
+        };
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(doc), ..
+        }) = &meta.value
+        {
+            lines.extend(doc.value().lines().map(str::to_owned));
+        }
+    }
+    lines
+}
+
+/// The documentation of the declaration of `function`.
+fn docs(function: &str) -> String {
+    let file = syn::parse_file(BINDINGS).unwrap();
+    for item in &file.items {
+        let block = match item {
+            Item::ForeignMod(block) => block,
+            _ => continue,
+        };
+        for item in &block.items {
+            match item {
+                ForeignItem::Fn(item) if item.sig.ident == function => {
+                    return doc_lines(&item.attrs).join("\n");
+                }
+                _ => {}
+            }
+        }
+    }
+    panic!("{} is not in the bindings", function)
+}
+
+#[test]
+fn functions_are_documented() {
+    for function in &[
+        "uiFreeAttribute",
+        "uiNewFamilyAttribute",
+        "uiNewAttributedString",
+        "uiAttributedStringString",
+    ] {
+        let docs = docs(function);
+        assert!(
+            docs.contains(function),
+            "{} is not documented, found {:?}",
+            function,
+            docs
+        );
+    }
+}
+
+fn assert_documented(function: &str, note: &str) {
+    let docs = docs(function);
+    assert!(
+        docs.contains(note),
+        "the docs of {} do not mention {:?}, found {:?}",
+        function,
+        note,
+        docs
+    );
+}
+
+#[test]
+fn ownership_notes_are_documented() {
+    // The font descriptor filled in by uiFontButtonFont has to be freed.
+    assert_documented("uiFontButtonFont", "`uiFreeFontButtonFont()`");
+    assert_documented("uiFreeFontButtonFont", "`uiFontButtonFont()`");
+    // Attributes own the strings they return, and the attributed string the
+    // attributes attached to it.
+    assert_documented("uiAttributeFamily", "owned by");
+    assert_documented("uiFreeAttribute", "ownership");
+}
+
+/// andlabs/libui leaves returned text undocumented.
+#[cfg(libui_ng)]
+#[test]
+fn freeing_returned_text_is_documented() {
+    for function in &["uiWindowTitle", "uiEntryText", "uiLabelText"] {
+        assert_documented(function, "`uiFreeText()`");
+    }
+}
+
+/// All lines of documentation in the bindings.
+#[derive(Default)]
+struct AllDocs(Vec<String>);
+
+impl<'ast> Visit<'ast> for AllDocs {
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        self.0.extend(doc_lines(std::slice::from_ref(attr)));
+    }
+}
+
+#[test]
+fn developer_notes_are_left_out() {
+    let mut docs = AllDocs::default();
+    docs.visit_file(&syn::parse_file(BINDINGS).unwrap());
+    assert!(docs.0.iter().any(|line| !line.is_empty()));
+
+    let notes: Vec<_> = docs
+        .0
+        .iter()
+        .filter(|line| line.trim_start().starts_with("TODO"))
+        .collect();
+    assert!(notes.is_empty(), "the bindings document {:?}", notes);
+}
+
+#[test]
+fn docs_are_line_comments() {
+    // Multi-line comments would otherwise come out as `/** */` blocks.
+    assert!(!BINDINGS.contains("/**"));
+}
@@ -0,0 +1,56 @@
+//! The enums are bound as newtypes, whose values have to stay those of
+//! `ui.h`.
+
+use libui_sys::*;
+
+#[test]
+fn closed_sets_keep_their_values() {
+    assert_eq!(uiDrawBrushType::uiDrawBrushTypeSolid.0, 0);
+    assert_eq!(uiDrawBrushType::uiDrawBrushTypeLinearGradient.0, 1);
+    assert_eq!(uiDrawBrushType::uiDrawBrushTypeRadialGradient.0, 2);
+    assert_eq!(uiDrawBrushType::uiDrawBrushTypeImage.0, 3);
+
+    assert_eq!(uiAlign::uiAlignFill.0, 0);
+    assert_eq!(uiAlign::uiAlignStart.0, 1);
+    assert_eq!(uiAlign::uiAlignCenter.0, 2);
+    assert_eq!(uiAlign::uiAlignEnd.0, 3);
+
+    assert_eq!(uiAt::uiAtLeading.0, 0);
+    assert_eq!(uiAt::uiAtTop.0, 1);
+    assert_eq!(uiAt::uiAtTrailing.0, 2);
+    assert_eq!(uiAt::uiAtBottom.0, 3);
+
+    assert_eq!(uiExtKey::uiExtKeyEscape.0, 1);
+    assert_eq!(uiExtKey::uiExtKeyInsert.0, 2);
+    assert_eq!(uiExtKey::uiExtKeyF1.0 + 11, uiExtKey::uiExtKeyF12.0);
+
+    assert_eq!(uiTextWeight::uiTextWeightMinimum.0, 0);
+    assert_eq!(uiTextWeight::uiTextWeightNormal.0, 400);
+    assert_eq!(uiTextWeight::uiTextWeightBold.0, 700);
+    assert_eq!(uiTextWeight::uiTextWeightMaximum.0, 1000);
+
+    assert_eq!(uiTableValueType::uiTableValueTypeString.0, 0);
+    assert_eq!(uiTableValueType::uiTableValueTypeImage.0, 1);
+    assert_eq!(uiTableValueType::uiTableValueTypeInt.0, 2);
+    assert_eq!(uiTableValueType::uiTableValueTypeColor.0, 3);
+
+    // Despite the name the edges are not flags.
+    assert_eq!(uiWindowResizeEdge::uiWindowResizeEdgeLeft.0, 0);
+    assert_eq!(uiWindowResizeEdge::uiWindowResizeEdgeBottomRight.0, 7);
+}
+
+#[test]
+fn flag_sets_combine() {
+    assert_eq!(uiModifiers::uiModifierCtrl.0, 1);
+    assert_eq!(uiModifiers::uiModifierAlt.0, 2);
+    assert_eq!(uiModifiers::uiModifierShift.0, 4);
+    assert_eq!(uiModifiers::uiModifierSuper.0, 8);
+
+    let modifiers = uiModifiers::uiModifierCtrl | uiModifiers::uiModifierShift;
+    assert_eq!(modifiers.0, 5);
+    assert_eq!(
+        (modifiers & uiModifiers::uiModifierShift).0,
+        uiModifiers::uiModifierShift.0
+    );
+    assert_eq!((modifiers & uiModifiers::uiModifierAlt).0, 0);
+}
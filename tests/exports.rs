@@ -0,0 +1,58 @@
+//! The bindings export nothing but the libui API.
+
+/// Names of the items the bindings declare at the top level, including the
+/// functions of extern blocks.
+fn exported_items() -> Vec<String> {
+    let bindings = include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+    let mut items = Vec::new();
+    let mut in_extern_block = false;
+    for line in bindings.lines() {
+        if line.ends_with("extern \"C\" {") && !line.starts_with(' ') {
+            in_extern_block = true;
+            continue;
+        }
+        if line == "}" {
+            in_extern_block = false;
+            continue;
+        }
+        let item = if in_extern_block {
+            line.strip_prefix("    ")
+        } else {
+            Some(line)
+        };
+        let item = match item.and_then(|item| item.strip_prefix("pub ")) {
+            Some(item) => item,
+            None => continue,
+        };
+        let mut words = item.split(|c: char| c.is_whitespace() || c == ':' || c == '(');
+        let kind = words.next().unwrap_or("");
+        if ["fn", "struct", "union", "enum", "type", "const", "static"].contains(&kind) {
+            items.push(words.next().unwrap_or("").to_owned());
+        }
+    }
+    items
+}
+
+/// Whether `name` is in libui's namespace, like `uiInit`, and unlike the
+/// `uint32_t` of stdint.h.
+fn is_libui_name(name: &str) -> bool {
+    name.starts_with("ui") && name[2..].starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+#[test]
+fn only_libui_items_are_exported() {
+    let items = exported_items();
+    assert!(items.iter().any(|item| item == "uiInit"));
+
+    let foreign: Vec<&String> = items
+        .iter()
+        .filter(|item| !is_libui_name(item))
+        // The dependency of uiDateTimePickerTime and the `dlopen` library.
+        .filter(|item| *item != "tm" && *item != "LibUi")
+        .collect();
+    assert!(
+        foreign.is_empty(),
+        "the bindings export items outside of libui: {:?}",
+        foreign
+    );
+}
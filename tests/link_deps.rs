@@ -0,0 +1,132 @@
+#[path = "../build/link_deps.rs"]
+mod link_deps;
+
+use link_deps::LinkDirective::{self, Framework, Lib, SearchPath};
+
+fn from_fixture(name: &str) -> Vec<LinkDirective> {
+    let path = format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let json = std::fs::read_to_string(path).unwrap();
+    link_deps::from_introspection(&json).unwrap()
+}
+
+fn libs(names: &[&str]) -> Vec<LinkDirective> {
+    names.iter().map(|name| Lib(name.to_string())).collect()
+}
+
+#[test]
+fn linux_dependencies() {
+    let expected = libs(&[
+        "gtk-3",
+        "gdk-3",
+        "pangocairo-1.0",
+        "pango-1.0",
+        "harfbuzz",
+        "atk-1.0",
+        "cairo-gobject",
+        "cairo",
+        "gdk_pixbuf-2.0",
+        "gio-2.0",
+        "gobject-2.0",
+        "glib-2.0",
+        "m",
+        "dl",
+        "pthread",
+    ]);
+    assert_eq!(from_fixture("introspect-dependencies-linux.json"), expected);
+}
+
+#[test]
+fn windows_msvc_dependencies() {
+    let expected = libs(&[
+        "user32",
+        "kernel32",
+        "gdi32",
+        "comctl32",
+        "uxtheme",
+        "msimg32",
+        "comdlg32",
+        "d2d1",
+        "dwrite",
+        "ole32",
+        "oleaut32",
+        "oleacc",
+        "uuid",
+        "windowscodecs",
+    ]);
+    assert_eq!(
+        from_fixture("introspect-dependencies-windows-msvc.json"),
+        expected
+    );
+}
+
+#[test]
+fn macos_dependencies() {
+    let expected = vec![
+        Framework("Foundation".to_string()),
+        Framework("AppKit".to_string()),
+    ];
+    assert_eq!(from_fixture("introspect-dependencies-macos.json"), expected);
+}
+
+#[test]
+fn duplicates_and_unknown_args_are_dropped() {
+    let args = [
+        "-lfoo",
+        "-Wl,--export-dynamic",
+        "-L/opt/lib",
+        "-lfoo",
+        "-L/opt/lib",
+    ];
+    assert_eq!(
+        link_deps::from_link_args(&args),
+        vec![Lib("foo".to_string()), SearchPath("/opt/lib".into())]
+    );
+}
+
+#[test]
+fn library_files_outside_linker_defaults_add_search_paths() {
+    let args = [
+        "/usr/lib/x86_64-linux-gnu/libm.so",
+        "/usr/lib64/libdl.so",
+        "/opt/gtk/lib/libgtk-3.so",
+        "/usr/local/lib/libfoo.a",
+    ];
+    assert_eq!(
+        link_deps::from_link_args(&args),
+        vec![
+            Lib("m".to_string()),
+            Lib("dl".to_string()),
+            SearchPath("/opt/gtk/lib".into()),
+            Lib("gtk-3".to_string()),
+            SearchPath("/usr/local/lib".into()),
+            Lib("foo".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn gtk_link_args() {
+    let args = link_deps::gtk_link_args(
+        &["/opt/gtk/lib".into()],
+        &["gtk-3".to_string(), "glib-2.0".to_string()],
+    );
+    assert_eq!(
+        args,
+        ["-L/opt/gtk/lib", "-lgtk-3", "-lglib-2.0", "-lm", "-ldl"]
+    );
+    assert_eq!(
+        link_deps::from_link_args(&args),
+        vec![
+            SearchPath("/opt/gtk/lib".into()),
+            Lib("gtk-3".to_string()),
+            Lib("glib-2.0".to_string()),
+            Lib("m".to_string()),
+            Lib("dl".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn invalid_json_is_an_error() {
+    assert!(link_deps::from_introspection("{\"name\": \"gtk+-3.0\"}").is_err());
+}
@@ -0,0 +1,86 @@
+//! libui-ng keeps the API of andlabs/libui: every item of the andlabs
+//! bindings, which the build generates next to the libui-ng ones when
+//! `LIBUI_SYS_COMPARE_ANDLABS` is set, is in the libui-ng ones, declared the
+//! same way.
+
+#![cfg(libui_andlabs)]
+
+use std::collections::BTreeMap;
+
+use syn::{Attribute, ForeignItem, ImplItem, Item};
+
+/// The items of the bindings in `code` by kind and name, without their
+/// documentation. Enum constants and the functions of extern blocks count
+/// as items of their own.
+fn items(code: &str) -> BTreeMap<String, String> {
+    fn undocumented(attrs: &mut Vec<Attribute>) {
+        attrs.retain(|attr| !attr.path().is_ident("doc"));
+    }
+
+    let file = syn::parse_file(code).unwrap();
+    let mut items = BTreeMap::new();
+    let mut add = |key: String, item: &dyn quote::ToTokens| {
+        items.insert(key, item.to_token_stream().to_string());
+    };
+    for mut item in file.items {
+        match &mut item {
+            Item::ForeignMod(block) => {
+                for item in &mut block.items {
+                    if let ForeignItem::Fn(function) = item {
+                        undocumented(&mut function.attrs);
+                        add(format!("fn {}", function.sig.ident), function);
+                    }
+                }
+            }
+            Item::Impl(block) => {
+                let ty = quote::ToTokens::to_token_stream(&block.self_ty).to_string();
+                for item in &mut block.items {
+                    if let ImplItem::Const(constant) = item {
+                        undocumented(&mut constant.attrs);
+                        add(format!("const {}::{}", ty, constant.ident), constant);
+                    }
+                }
+            }
+            Item::Struct(item) => {
+                undocumented(&mut item.attrs);
+                for field in item.fields.iter_mut() {
+                    undocumented(&mut field.attrs);
+                }
+                add(format!("struct {}", item.ident), item);
+            }
+            Item::Type(item) => {
+                undocumented(&mut item.attrs);
+                add(format!("type {}", item.ident), item);
+            }
+            Item::Const(item) => {
+                undocumented(&mut item.attrs);
+                add(format!("const {}", item.ident), item);
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+#[test]
+fn andlabs_api_is_unchanged() {
+    let andlabs = items(include_str!(concat!(env!("OUT_DIR"), "/andlabs.rs")));
+    let ng = items(include_str!(concat!(env!("OUT_DIR"), "/bindings.rs")));
+
+    let mut differences = Vec::new();
+    for (key, declaration) in &andlabs {
+        match ng.get(key) {
+            None => differences.push(format!("{} is missing", key)),
+            Some(ng) if ng != declaration => differences.push(format!(
+                "{} changed from `{}` to `{}`",
+                key, declaration, ng
+            )),
+            Some(_) => {}
+        }
+    }
+    assert!(
+        differences.is_empty(),
+        "the libui-ng bindings differ from the andlabs/libui ones:\n{}",
+        differences.join("\n")
+    );
+}
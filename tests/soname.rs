@@ -0,0 +1,44 @@
+#![cfg(libui_versioned_soname)]
+
+use std::env;
+use std::fs;
+use std::hint::black_box;
+
+use object::read::elf::{Dyn, ElfFile32, ElfFile64, FileHeader};
+use object::{elf, Endianness, FileKind};
+
+#[test]
+fn binaries_need_the_versioned_soname() {
+    // Keeps the linker from dropping libui as unused.
+    black_box(libui_sys::uiMain as unsafe extern "C" fn());
+
+    let exe = env::current_exe().unwrap();
+    let data = fs::read(&exe).unwrap();
+    let needed = match FileKind::parse(&*data).unwrap() {
+        FileKind::Elf32 => needed(&ElfFile32::<Endianness>::parse(&*data).unwrap(), &data),
+        FileKind::Elf64 => needed(&ElfFile64::<Endianness>::parse(&*data).unwrap(), &data),
+        kind => panic!("{} is not an ELF file but {:?}", exe.display(), kind),
+    };
+    assert!(
+        needed.iter().any(|library| library == "libui.so.0"),
+        "libui.so.0 is not needed by {}, only {:?}",
+        exe.display(),
+        needed
+    );
+}
+
+/// The `DT_NEEDED` entries of the dynamic section of `file`.
+fn needed<Elf: FileHeader>(file: &object::read::elf::ElfFile<Elf>, data: &[u8]) -> Vec<String> {
+    let endian = file.endian();
+    let sections = file.elf_section_table();
+    let (dynamic, link) = sections
+        .dynamic(endian, data)
+        .unwrap()
+        .expect("the test binary has no dynamic section");
+    let strings = sections.strings(endian, data, link).unwrap();
+    dynamic
+        .iter()
+        .filter(|entry| entry.tag32(endian) == Some(elf::DT_NEEDED))
+        .map(|entry| String::from_utf8_lossy(entry.string(endian, strings).unwrap()).into_owned())
+        .collect()
+}
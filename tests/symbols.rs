@@ -0,0 +1,135 @@
+//! Reading the functions a library exports from its symbol table, and what
+//! the build made of the library it built or found.
+
+#[path = "../build/symbol_table.rs"]
+mod symbol_table;
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use libui_sys::IMPLEMENTED_FUNCTIONS;
+
+/// The libui functions the build found in the bindings, in extern blocks or
+/// as methods of the `dlopen` library struct.
+const DECLARED_FUNCTIONS: &[&str] = &include!(concat!(env!("OUT_DIR"), "/declared.rs"));
+
+/// The symbols read from the fixture `name` in `tests/data/symbols`.
+///
+/// The fixtures define `uiInit` and `uiMain`, and were made from a small C
+/// file or its assembly with:
+///
+/// ```text
+/// gcc -fPIC -shared -nostdlib -s -Wl,-soname,libui.so.0 -o elf-shared.so.0 ui.c
+/// gcc -c ui.c && ar rcs elf-static.a ui.o notes.txt
+/// llvm-mc -triple x86_64-apple-macos10.12 -filetype=obj -o ui.o ui.s
+/// llvm-ar rcs --format=darwin macos-static.a ui.o
+/// llvm-mc -triple i686-pc-windows-msvc -filetype=obj -o ui.obj ui.s
+/// llvm-ar rcs --format=gnu i686-windows-static.lib ui.obj
+/// llvm-dlltool -m i386 -d ui.def -l i686-windows-import.dll.a
+/// llvm-dlltool -m i386:x86-64 -d ui.def -l x86_64-windows-import.dll.a
+/// ```
+fn symbols(name: &str) -> BTreeSet<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("symbols")
+        .join(name);
+    symbol_table::exported_symbols(&path).unwrap()
+}
+
+fn assert_functions(symbols: &BTreeSet<String>, expected: &[&str], unexpected: &[&str]) {
+    for function in expected {
+        assert!(
+            symbols.contains(*function),
+            "{} is missing from {:?}",
+            function,
+            symbols
+        );
+    }
+    for function in unexpected {
+        assert!(
+            !symbols.contains(*function),
+            "{} should not be in {:?}",
+            function,
+            symbols
+        );
+    }
+}
+
+#[test]
+fn shared_library_exports() {
+    // Only what the library exports counts, not hidden, local or undefined
+    // symbols.
+    let symbols = symbols("elf-shared.so.0");
+    assert_functions(
+        &symbols,
+        &["uiInit", "uiMain"],
+        &["uiPrivateHelper", "helper", "uiUndefined"],
+    );
+}
+
+#[test]
+fn static_library_definitions() {
+    // The archive also has a member that is not an object file.
+    let symbols = symbols("elf-static.a");
+    assert_functions(&symbols, &["uiInit", "uiMain"], &["helper", "uiUndefined"]);
+}
+
+#[test]
+fn macos_symbols_lose_their_underscore() {
+    let symbols = symbols("macos-static.a");
+    assert_eq!(
+        symbols,
+        ["uiInit", "uiMain"].iter().map(|s| s.to_string()).collect()
+    );
+}
+
+#[test]
+fn i686_windows_symbols_lose_their_underscore() {
+    let symbols = symbols("i686-windows-static.lib");
+    assert_eq!(
+        symbols,
+        ["uiInit", "uiMain"].iter().map(|s| s.to_string()).collect()
+    );
+}
+
+#[test]
+fn import_library_members() {
+    assert_functions(
+        &symbols("i686-windows-import.dll.a"),
+        &["uiInit", "uiMain"],
+        &["_uiInit", "_uiMain"],
+    );
+    assert_functions(
+        &symbols("x86_64-windows-import.dll.a"),
+        &["uiInit", "uiMain"],
+        &[],
+    );
+}
+
+/// Functions of `ui.h` may be missing from some backends, which the build
+/// warns about. Those are only reported here, but the core of the API has to
+/// be there.
+#[test]
+fn core_functions_are_implemented() {
+    let implemented = match IMPLEMENTED_FUNCTIONS {
+        Some(implemented) => implemented,
+        None => return,
+    };
+    for function in &["uiInit", "uiUninit", "uiMain", "uiQuit", "uiNewWindow"] {
+        assert!(DECLARED_FUNCTIONS.contains(function));
+        assert!(
+            implemented.contains(function),
+            "libui does not implement {}",
+            function
+        );
+    }
+
+    let missing: Vec<_> = DECLARED_FUNCTIONS
+        .iter()
+        .filter(|function| !implemented.contains(function))
+        .collect();
+    if !missing.is_empty() {
+        eprintln!("libui does not implement {:?}", missing);
+    }
+}